@@ -0,0 +1,220 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Global allocator counting the allocations of every thread while counting
+/// is enabled, otherwise it just forwards to the system allocator.
+/// A binary installs it with
+/// `#[global_allocator] static ALLOCATOR: CountingAllocator = CountingAllocator;`
+pub struct CountingAllocator;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Allocations made while running some code
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct AllocStats {
+    pub allocations: u64,
+    /// Total number of bytes allocated, memory that got freed is included
+    pub bytes: u64,
+    /// Highest number of bytes allocated at the same time
+    pub peak: u64,
+    /// Bytes still allocated at the end, e.g. by a parsed input
+    pub retained: u64,
+}
+
+impl AllocStats {
+    /// Allocations of running some code and then the code of `next`,
+    /// while what this code retained is still allocated
+    pub fn then(self, next: AllocStats) -> AllocStats {
+        AllocStats {
+            allocations: self.allocations + next.allocations,
+            bytes: self.bytes + next.bytes,
+            peak: self.peak.max(self.retained + next.peak),
+            retained: self.retained + next.retained,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Counter {
+    allocations: u64,
+    bytes: u64,
+    /// Can become negative if memory allocated earlier is freed
+    live: i64,
+    peak: i64,
+}
+
+impl Counter {
+    const ZERO: Counter = Counter {
+        allocations: 0,
+        bytes: 0,
+        live: 0,
+        peak: 0,
+    };
+
+    fn allocated(&mut self, size: usize) {
+        self.allocations += 1;
+        self.bytes += size as u64;
+        self.live += size as i64;
+        self.peak = self.peak.max(self.live);
+    }
+
+    fn freed(&mut self, size: usize) {
+        self.live -= size as i64;
+    }
+}
+
+thread_local! {
+    // const initialized without destructor, accessing it never allocates
+    static COUNTER: Cell<Counter> = const { Cell::new(Counter::ZERO) };
+}
+
+fn count<F: FnOnce(&mut Counter)>(f: F) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // fails only while the thread is being torn down
+    let _ = COUNTER.try_with(|counter| {
+        let mut current = counter.get();
+        f(&mut current);
+        counter.set(current);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            count(|counter| counter.allocated(layout.size()));
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            count(|counter| counter.allocated(layout.size()));
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        count(|counter| counter.freed(layout.size()));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            count(|counter| {
+                counter.freed(layout.size());
+                counter.allocated(new_size);
+            });
+        }
+        new_ptr
+    }
+}
+
+/// Start counting allocations, only has an effect if [`CountingAllocator`]
+/// is the global allocator
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Run `f` and count the allocations it makes on the current thread
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, AllocStats) {
+    let outer = COUNTER.with(|counter| counter.replace(Counter::ZERO));
+    let result = f();
+    let inner = COUNTER.with(|counter| counter.get());
+
+    // keep counting for an enclosing measurement
+    COUNTER.with(|counter| {
+        counter.set(Counter {
+            allocations: outer.allocations + inner.allocations,
+            bytes: outer.bytes + inner.bytes,
+            live: outer.live + inner.live,
+            peak: outer.peak.max(outer.live + inner.peak),
+        })
+    });
+
+    let stats = AllocStats {
+        allocations: inner.allocations,
+        bytes: inner.bytes,
+        peak: inner.peak.max(0) as u64,
+        retained: inner.live.max(0) as u64,
+    };
+    (result, stats)
+}
+
+/// Human readable size like "512B" or "1.5MiB"
+pub fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes}B"),
+        1024..=1_048_575 => format!("{:.1}KiB", bytes as f64 / 1024.0),
+        1_048_576..=1_073_741_823 => format!("{:.1}MiB", bytes as f64 / 1_048_576.0),
+        _ => format!("{:.1}GiB", bytes as f64 / 1_073_741_824.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn counts_allocations_of_the_current_thread() {
+        enable();
+        let (len, stats) = measure(|| {
+            let a = vec![0u8; 100];
+            let (_, inner) = measure(|| vec![0u8; 50]);
+            assert_eq!(inner.allocations, 1);
+            assert_eq!(inner.retained, 50);
+            drop(a);
+            vec![1u8; 10].len()
+        });
+
+        assert_eq!(len, 10);
+        assert_eq!(
+            stats,
+            AllocStats {
+                allocations: 3,
+                bytes: 160,
+                peak: 150,
+                retained: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn adds_up_allocations_of_consecutive_code() {
+        let parse = AllocStats {
+            allocations: 2,
+            bytes: 100,
+            peak: 80,
+            retained: 60,
+        };
+        let solve = AllocStats {
+            allocations: 1,
+            bytes: 30,
+            peak: 30,
+            retained: 0,
+        };
+        assert_eq!(
+            parse.then(solve),
+            AllocStats {
+                allocations: 3,
+                bytes: 130,
+                peak: 90,
+                retained: 60,
+            }
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use std::{
+    fmt::{self, Write},
+    fs,
+    io::{self, BufRead, ErrorKind, Read},
+    path::Path,
+};
+
+use crate::{
+    error::{read_error, Error, Result},
+    solution::Part,
+};
+
+pub const DEFAULT_PATH: &str = "data/answers.txt";
+
+/// Accepted answers and submitted guesses per day, part and puzzle input
+///
+/// Stored as a plain text file with one tab separated entry per line:
+/// `<year> <day> <part> <input hash> <answer> [<verdict>]`,
+/// entries without a verdict are correct answers.
+pub struct Answers {
+    path: String,
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    year: u32,
+    day: u32,
+    part: Part,
+    input: u64,
+    answer: String,
+    verdict: Verdict,
+}
+
+/// What the website said about a submitted answer
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong without a hint in which direction
+    Wrong,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+impl Answers {
+    /// Load the answers file, a missing file is treated as empty
+    pub fn load(path: &str) -> Result<Answers> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::from(""),
+            Err(source) => {
+                return Err(Error::Io {
+                    path: path.to_string(),
+                    source,
+                })
+            }
+        };
+
+        let mut entries = vec![];
+        for (i, line) in content.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            entries.push(parse_entry(line).ok_or_else(|| {
+                Error::parse(
+                    i + 1,
+                    1,
+                    "expected '<year>\t<day>\t<part>\t<input hash>\t<answer>[\t<verdict>]'",
+                )
+            })?);
+        }
+
+        Ok(Answers {
+            path: path.to_string(),
+            entries,
+        })
+    }
+
+    /// The correct answer, if it is known
+    pub fn get(&self, year: u32, day: u32, part: Part, input: &str) -> Option<&str> {
+        self.get_hashed(year, day, part, input_hash(input))
+    }
+
+    /// Same as [`Answers::get`] for an input known by its [`input_hash`]
+    pub fn get_hashed(&self, year: u32, day: u32, part: Part, hash: u64) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.matches(year, day, part, hash) && e.verdict == Verdict::Correct)
+            .map(|e| e.answer.as_str())
+    }
+
+    /// Whether a correct answer is known for any input, i.e. the star was earned
+    pub fn is_solved(&self, year: u32, day: u32, part: Part) -> bool {
+        self.entries
+            .iter()
+            .any(|e| (e.year, e.day, e.part) == (year, day, part) && e.verdict == Verdict::Correct)
+    }
+
+    /// Reason why submitting `answer` is pointless, if the recorded answers
+    /// already tell that it's wrong or the part is solved
+    pub fn rejection(
+        &self,
+        year: u32,
+        day: u32,
+        part: Part,
+        input: &str,
+        answer: &str,
+    ) -> Option<String> {
+        self.rejection_hashed(year, day, part, input_hash(input), answer)
+    }
+
+    /// Same as [`Answers::rejection`] for an input known by its [`input_hash`]
+    pub fn rejection_hashed(
+        &self,
+        year: u32,
+        day: u32,
+        part: Part,
+        hash: u64,
+        answer: &str,
+    ) -> Option<String> {
+        if let Some(known) = self.get_hashed(year, day, part, hash) {
+            return Some(format!("part is already solved, the answer is {known}"));
+        }
+
+        let value = answer.parse::<i64>().ok();
+        self.entries
+            .iter()
+            .filter(|e| e.matches(year, day, part, hash))
+            .find_map(|e| {
+                let bound = e.answer.parse::<i64>().ok();
+                let out_of_bounds = match (e.verdict, value, bound) {
+                    (Verdict::TooHigh, Some(value), Some(high)) => value >= high,
+                    (Verdict::TooLow, Some(value), Some(low)) => value <= low,
+                    _ => false,
+                };
+                match e.answer == answer || out_of_bounds {
+                    true => Some(format!("{} was {}", e.answer, e.verdict)),
+                    false => None,
+                }
+            })
+    }
+
+    /// Record the accepted answer, replacing a previously recorded one
+    pub fn record(&mut self, year: u32, day: u32, part: Part, input: &str, answer: &str) {
+        self.record_hashed(year, day, part, input_hash(input), answer)
+    }
+
+    /// Same as [`Answers::record`] for an input known by its [`input_hash`]
+    pub fn record_hashed(&mut self, year: u32, day: u32, part: Part, hash: u64, answer: &str) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|e| e.matches(year, day, part, hash) && e.verdict == Verdict::Correct)
+        {
+            Some(entry) => entry.answer = answer.to_string(),
+            None => self.entries.push(Entry {
+                year,
+                day,
+                part,
+                input: hash,
+                answer: answer.to_string(),
+                verdict: Verdict::Correct,
+            }),
+        }
+    }
+
+    /// Record the verdict about a submitted answer
+    pub fn record_guess(
+        &mut self,
+        year: u32,
+        day: u32,
+        part: Part,
+        input: &str,
+        answer: &str,
+        verdict: Verdict,
+    ) {
+        if verdict == Verdict::Correct {
+            return self.record(year, day, part, input, answer);
+        }
+
+        let hash = input_hash(input);
+        if !self
+            .entries
+            .iter()
+            .any(|e| e.matches(year, day, part, hash) && e.answer == answer)
+        {
+            self.entries.push(Entry {
+                year,
+                day,
+                part,
+                input: hash,
+                answer: answer.to_string(),
+                verdict,
+            });
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let content = self.entries.iter().fold(String::from(""), |mut str, e| {
+            let _ = write!(
+                str,
+                "{}\t{}\t{}\t{:016x}\t{}",
+                e.year, e.day, e.part, e.input, e.answer
+            );
+            let _ = match e.verdict {
+                Verdict::Correct => writeln!(str),
+                Verdict::TooHigh => writeln!(str, "\thigh"),
+                Verdict::TooLow => writeln!(str, "\tlow"),
+                Verdict::Wrong => writeln!(str, "\twrong"),
+            };
+            str
+        });
+
+        let dir = Path::new(&self.path)
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty());
+        dir.map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&self.path, content))
+            .map_err(|source| Error::Io {
+                path: self.path.clone(),
+                source,
+            })
+    }
+}
+
+impl Entry {
+    fn matches(&self, year: u32, day: u32, part: Part, input: u64) -> bool {
+        self.year == year && self.day == day && self.part == part && self.input == input
+    }
+}
+
+fn parse_entry(line: &str) -> Option<Entry> {
+    let mut columns = line.split('\t');
+    Some(Entry {
+        year: columns.next()?.parse().ok()?,
+        day: columns.next()?.parse().ok()?,
+        part: match columns.next()? {
+            "1" => Part::One,
+            "2" => Part::Two,
+            _ => return None,
+        },
+        input: u64::from_str_radix(columns.next()?, 16).ok()?,
+        answer: columns.next()?.to_string(),
+        verdict: match columns.next() {
+            None => Verdict::Correct,
+            Some("high") => Verdict::TooHigh,
+            Some("low") => Verdict::TooLow,
+            Some("wrong") => Verdict::Wrong,
+            Some(_) => return None,
+        },
+    })
+}
+
+/// FNV-1a hash identifying a puzzle input, so answers of different inputs
+/// (e.g. of different team members) don't get mixed up
+pub fn input_hash(input: &str) -> u64 {
+    fnv1a(FNV_OFFSET, input.as_bytes())
+}
+
+/// Reader computing the [`input_hash`] of everything read through it,
+/// so an input can be solved and hashed in the same pass
+pub struct HashingReader<R> {
+    inner: R,
+    hash: u64,
+}
+
+impl<R: BufRead> HashingReader<R> {
+    pub fn new(inner: R) -> HashingReader<R> {
+        HashingReader {
+            inner,
+            hash: FNV_OFFSET,
+        }
+    }
+
+    /// Hash of the whole input, reads what wasn't read yet
+    pub fn finish(mut self) -> Result<u64> {
+        loop {
+            let len = self.fill_buf().map_err(read_error)?.len();
+            if len == 0 {
+                return Ok(self.hash);
+            }
+            self.consume(len);
+        }
+    }
+}
+
+impl<R: BufRead> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.hash = fnv1a(self.hash, &buf[..len]);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for HashingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // the consumed bytes are still buffered, getting them doesn't read
+        if let Ok(buffer) = self.inner.fill_buf() {
+            self.hash = fnv1a(self.hash, &buffer[..amt.min(buffer.len())]);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+/// Continue the hash of the bytes before these ones
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_known_wrong_and_out_of_bounds_answers() {
+        let mut answers = Answers {
+            path: String::from(""),
+            entries: vec![],
+        };
+        let record = |answers: &mut Answers, answer, verdict| {
+            answers.record_guess(2022, 1, Part::One, "input", answer, verdict)
+        };
+        let rejection =
+            |answers: &Answers, answer| answers.rejection(2022, 1, Part::One, "input", answer);
+
+        record(&mut answers, "100", Verdict::TooHigh);
+        record(&mut answers, "10", Verdict::TooLow);
+        record(&mut answers, "50", Verdict::Wrong);
+        assert_eq!(
+            rejection(&answers, "100"),
+            Some(String::from("100 was too high"))
+        );
+        assert_eq!(
+            rejection(&answers, "150"),
+            Some(String::from("100 was too high"))
+        );
+        assert_eq!(
+            rejection(&answers, "5"),
+            Some(String::from("10 was too low"))
+        );
+        assert_eq!(
+            rejection(&answers, "50"),
+            Some(String::from("50 was wrong"))
+        );
+        assert_eq!(rejection(&answers, "42"), None);
+        assert_eq!(answers.rejection(2022, 1, Part::Two, "input", "150"), None);
+        assert_eq!(answers.rejection(2022, 1, Part::One, "other", "150"), None);
+
+        record(&mut answers, "42", Verdict::Correct);
+        assert_eq!(answers.get(2022, 1, Part::One, "input"), Some("42"));
+        assert!(rejection(&answers, "43").is_some());
+    }
+
+    #[test]
+    fn hashes_inputs_read_in_pieces() {
+        let input = "1000\n2000\n\n3000\n";
+        let reader = io::BufReader::with_capacity(3, input.as_bytes());
+        assert_eq!(
+            HashingReader::new(reader).finish().unwrap(),
+            input_hash(input)
+        );
+
+        // partly read by lines and partly by finishing
+        let mut reader = HashingReader::new(io::BufReader::with_capacity(3, input.as_bytes()));
+        let mut line = String::from("");
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "1000\n");
+        assert_eq!(reader.finish().unwrap(), input_hash(input));
+        assert_ne!(input_hash(input), input_hash("1000\n"));
+    }
+}
@@ -0,0 +1,268 @@
+use std::{
+    fmt::{self, Write},
+    fs,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::{read_input, stream_input, Error, Result},
+    solution::{Part, Runner},
+};
+
+/// What is being measured for a day
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+    /// Reading and solving a part at once, for days that stream their input
+    Stream(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve(part) => write!(f, "part{part}"),
+            Phase::Stream(part) => write!(f, "stream{part}"),
+        }
+    }
+}
+
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarize the given samples, there has to be at least one
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort();
+
+        let n = samples.len() as f64;
+        let mean = samples.iter().map(|d| d.as_secs_f64()).sum::<f64>() / n;
+        let variance = samples
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n;
+
+        let mid = samples.len() / 2;
+        let median = match samples.len() % 2 {
+            0 => (samples[mid - 1] + samples[mid]) / 2,
+            _ => samples[mid],
+        };
+
+        Stats {
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+pub struct Measurement {
+    pub year: u32,
+    pub day: u32,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// Measure parsing and solving of a day separately,
+/// every phase is run `warmup` times before `runs` samples are taken.
+/// Days that can stream their input are measured reading and solving each
+/// part straight from the file, see [`Runner::streaming`] and [`Phase::Stream`].
+pub fn bench_day(
+    day: &dyn Runner,
+    filename: &str,
+    parts: &[Part],
+    runs: usize,
+    warmup: usize,
+) -> Result<Vec<Measurement>> {
+    let measurement = |phase, stats| Measurement {
+        year: day.year(),
+        day: day.day(),
+        phase,
+        stats,
+    };
+
+    if let Some(solve) = day.streaming() {
+        return parts
+            .iter()
+            .map(|part| {
+                let stats = measure(runs, warmup, || {
+                    stream_input(filename, |mut reader| solve(&mut reader, *part)).map(black_box)
+                })?;
+                Ok(measurement(Phase::Stream(*part), stats))
+            })
+            .collect();
+    }
+
+    let input = read_input(filename)?;
+    let mut measurements = vec![measurement(
+        Phase::Parse,
+        measure(runs, warmup, || day.parse(&input).map(black_box))?,
+    )];
+
+    let parsed = day.parse(&input)?;
+    for part in parts {
+        measurements.push(measurement(
+            Phase::Solve(*part),
+            measure(runs, warmup, || {
+                day.solve(parsed.as_ref(), *part).map(black_box)
+            })?,
+        ));
+    }
+
+    Ok(measurements)
+}
+
+/// [`bench_day`] for every day in order, lazily so the measurements can be shown
+/// as they come. Errors are messages naming the input file.
+pub fn bench_days<'a, F>(
+    days: &'a [&'static dyn Runner],
+    parts: &'a [Part],
+    input_file: F,
+    runs: usize,
+    warmup: usize,
+) -> impl Iterator<
+    Item = (
+        &'static dyn Runner,
+        std::result::Result<Vec<Measurement>, String>,
+    ),
+> + 'a
+where
+    F: Fn(&dyn Runner) -> String + 'a,
+{
+    days.iter().map(move |day| {
+        let filename = input_file(*day);
+        let measurements = bench_day(*day, &filename, parts, runs, warmup);
+        (*day, measurements.map_err(|err| err.in_file(&filename)))
+    })
+}
+
+fn measure<T, F>(runs: usize, warmup: usize, mut f: F) -> Result<Stats>
+where
+    F: FnMut() -> Result<T>,
+{
+    for _ in 0..warmup {
+        f()?;
+    }
+
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+
+    Ok(Stats::from_samples(&mut samples))
+}
+
+/// Median durations of a previous benchmark run
+pub struct Baseline {
+    entries: Vec<(u32, u32, String, Duration)>,
+}
+
+impl Baseline {
+    pub fn load(path: &str) -> Result<Baseline> {
+        let content = fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.to_string(),
+            source,
+        })?;
+
+        let mut entries = vec![];
+        for (i, line) in content.lines().enumerate() {
+            entries.push(parse_entry(line).ok_or_else(|| {
+                Error::parse(i + 1, 1, "expected '<year>\t<day>\t<phase>\t<median ns>'")
+            })?);
+        }
+
+        Ok(Baseline { entries })
+    }
+
+    pub fn median(&self, year: u32, day: u32, phase: Phase) -> Option<Duration> {
+        let phase = phase.to_string();
+        self.entries
+            .iter()
+            .find(|(y, d, p, _)| *y == year && *d == day && *p == phase)
+            .map(|(_, _, _, median)| *median)
+    }
+}
+
+fn parse_entry(line: &str) -> Option<(u32, u32, String, Duration)> {
+    let mut columns = line.split('\t');
+    let year = columns.next()?.parse::<u32>().ok()?;
+    let day = columns.next()?.parse::<u32>().ok()?;
+    let phase = columns.next()?.to_string();
+    let nanos = columns.next()?.parse::<u64>().ok()?;
+    Some((year, day, phase, Duration::from_nanos(nanos)))
+}
+
+/// Save the medians of the measurements as a baseline for later comparisons
+pub fn save_baseline(path: &str, measurements: &[Measurement]) -> Result<()> {
+    let content = measurements.iter().fold(String::from(""), |mut str, m| {
+        let _ = writeln!(
+            str,
+            "{}\t{}\t{}\t{}",
+            m.year,
+            m.day,
+            m.phase,
+            m.stats.median.as_nanos()
+        );
+        str
+    });
+
+    fs::write(path, content).map_err(|source| Error::Io {
+        path: path.to_string(),
+        source,
+    })
+}
+
+pub fn print_header(with_baseline: bool) {
+    print!(
+        "{:>4} {:>3}  {:<7} {:>10} {:>10} {:>10} {:>10}",
+        "year", "day", "phase", "min", "median", "mean", "stddev"
+    );
+    if with_baseline {
+        print!(" {:>10} {:>8}", "baseline", "change");
+    }
+    println!();
+}
+
+pub fn print_measurement(m: &Measurement, baseline: Option<&Baseline>) {
+    print!(
+        "{:>4} {:>3}  {:<7} {:>10} {:>10} {:>10} {:>10}",
+        m.year,
+        m.day,
+        m.phase.to_string(),
+        format_duration(m.stats.min),
+        format_duration(m.stats.median),
+        format_duration(m.stats.mean),
+        format_duration(m.stats.stddev)
+    );
+    if let Some(baseline) = baseline {
+        match baseline.median(m.year, m.day, m.phase) {
+            Some(base) => {
+                let change = (m.stats.median.as_secs_f64() / base.as_secs_f64() - 1.0) * 100.0;
+                print!(" {:>10} {:>+7.1}%", format_duration(base), change);
+            }
+            None => print!(" {:>10} {:>8}", "-", "-"),
+        }
+    }
+    println!();
+}
+
+/// Human readable duration with an appropriate unit
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    match nanos {
+        0..=999 => format!("{nanos}ns"),
+        1_000..=999_999 => format!("{:.1}µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.2}ms", nanos as f64 / 1e6),
+        _ => format!("{:.2}s", nanos as f64 / 1e9),
+    }
+}
@@ -0,0 +1,800 @@
+use std::time::{Duration, SystemTime};
+
+use adventofcode::{answers, input_file, output::Format, prop, solution::Part};
+
+pub const USAGE: &str = "\
+Usage: adventofcode [COMMAND] [OPTIONS]
+
+Commands:
+  run     Run the selected days (default)
+  verify  Compare the answers of the selected days with the known answers
+  bench   Measure parsing and solving times of the selected days
+  check   Validate the inputs of the selected days without solving them,
+          showing the line and column of every problem
+  new-day <DAY> [--year <YEAR>] [--title <TITLE>]
+          Generate the module and an empty input file for a new day
+          (default year: the latest year with registered days)
+  fetch -d <DAYS> [--year <YEARS>]
+          Download missing puzzle inputs to their default location,
+          using the session token in AOC_SESSION or in
+          ~/.config/adventofcode/session (default year: as for new-day)
+  submit -d <DAY> -p <PART>
+          Solve a part and submit the answer, unless the recorded
+          answers show that it's wrong (session token as for fetch)
+  watch -d <DAY> [--example <FILE>] [--interval <MS>]
+          Run a day again whenever its input (or the example file)
+          changes, checking every 500ms by default
+  report [--no-run] [-o <FILE>]
+          Write a Markdown table with the stars, verified answers and
+          times of the selected days and a total per year
+  repl -d <DAY>
+          Load the input of a day and explore it with day specific
+          commands, enter help for the commands of the day
+  generate -d <DAY> [--size <N>] [--seed <N>] [-o <FILE>]
+          Print a random input of a day that its parser accepts, the
+          lists in it have N elements on average (default: 100)
+  diff [--cases <N>] [--seed <N>]
+          Run every implementation of the selected parts on N generated
+          inputs (default: 2000) and show the smallest input they
+          disagree on
+  list    List all available days
+  help    Print this message
+
+Options:
+  -y, --year <YEARS>   Year(s) to run, e.g. 2022 or 2015-2017 (default: all)
+  -d, --day <DAYS>     Day(s) to run, e.g. 7, 1-3 or 1,4-6 (default: all)
+  -p, --part <PART>    Only run part 1 or 2 (default: both)
+  -i, --input <FILE>   Input file, only valid for a single day
+                       (default: data/<YEAR>/<DAY>.txt)
+  -j, --jobs <N>       Solve up to N parts in parallel when running,
+                       verifying or reporting, bounded by the number of
+                       CPUs, 0 for one per CPU (default: 1)
+  -f, --format <FMT>   Print the results of run and verify as text, json
+                       or csv (default: text)
+  -a, --alloc          Count the allocations and peak memory of every part
+                       when running, verifying or watching
+
+Run options:
+  -e, --examples       Check the puzzle examples instead of the input
+
+Verify options:
+  --answers <FILE>     Known answers file (default: data/answers.txt)
+  --accept             Record new answers as accepted
+
+Submit options:
+  --answer <ANSWER>    Submit this answer instead of solving the part
+  --answers <FILE>     Answers file to check and record the answer in
+                       (default: data/answers.txt)
+
+Report options:
+  --answers <FILE>     Known answers file (default: data/answers.txt)
+  --no-run             Only report the stars of the answers file
+  -o, --output <FILE>  Write the report to a file instead of printing it
+
+Bench options:
+  --runs <N>           Number of measured runs (default: 10)
+  --warmup <N>         Number of unmeasured runs before measuring (default: 2)
+  --save <FILE>        Save the median times as a baseline
+  --baseline <FILE>    Compare the median times with a saved baseline";
+
+pub enum Command {
+    Run(RunOptions),
+    Verify(VerifyOptions),
+    Bench(BenchOptions),
+    Check(RunOptions),
+    NewDay {
+        year: Option<u32>,
+        day: u32,
+        title: String,
+    },
+    Submit(SubmitOptions),
+    Watch(WatchOptions),
+    Report(ReportOptions),
+    /// Selects exactly one day
+    Repl(RunOptions),
+    Generate(GenerateOptions),
+    Diff(DiffOptions),
+    Fetch {
+        /// Selected years, empty for the latest year
+        years: Vec<u32>,
+        days: Vec<u32>,
+    },
+    List,
+    Help,
+}
+
+pub struct RunOptions {
+    /// Selected years, empty if all years should run
+    pub years: Vec<u32>,
+    /// Selected days, empty if all days should run
+    pub days: Vec<u32>,
+    /// Selected part, both parts if none
+    pub part: Option<Part>,
+    pub input: Option<String>,
+    /// Check the examples instead of solving the input
+    pub examples: bool,
+    /// Number of parts to solve in parallel, 0 for one per CPU
+    pub jobs: usize,
+    pub format: Format,
+    /// Count allocations while solving
+    pub alloc: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> RunOptions {
+        RunOptions {
+            years: vec![],
+            days: vec![],
+            part: None,
+            input: None,
+            examples: false,
+            jobs: 1,
+            format: Format::Text,
+            alloc: false,
+        }
+    }
+}
+
+impl RunOptions {
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![Part::One, Part::Two],
+        }
+    }
+
+    pub fn input_file(&self, year: u32, day: u32) -> String {
+        match &self.input {
+            Some(input) => input.clone(),
+            None => input_file(year, day),
+        }
+    }
+}
+
+pub struct VerifyOptions {
+    pub run: RunOptions,
+    pub answers: String,
+    /// Record answers of days without a known answer
+    pub accept: bool,
+}
+
+pub struct ReportOptions {
+    pub run: RunOptions,
+    pub answers: String,
+    /// Don't execute the days, take only the stars from the answers file
+    pub no_run: bool,
+    /// File to write the report to instead of printing it
+    pub output: Option<String>,
+}
+
+pub struct BenchOptions {
+    pub run: RunOptions,
+    pub runs: usize,
+    pub warmup: usize,
+    /// File to save the results to as a baseline
+    pub save: Option<String>,
+    /// Previously saved baseline to compare the results with
+    pub baseline: Option<String>,
+}
+
+pub struct SubmitOptions {
+    /// Selects exactly one day and part
+    pub run: RunOptions,
+    /// Answer to submit instead of the solved one
+    pub answer: Option<String>,
+    pub answers: String,
+}
+
+pub struct WatchOptions {
+    /// Selects exactly one day
+    pub run: RunOptions,
+    /// Example input to run as well
+    pub example: Option<String>,
+    /// Time between two checks for modifications
+    pub interval: Duration,
+}
+
+pub struct GenerateOptions {
+    /// Selects exactly one day
+    pub run: RunOptions,
+    /// Average length of the lists in the input
+    pub size: usize,
+    pub seed: u64,
+    /// File to write the input to instead of printing it
+    pub output: Option<String>,
+}
+
+pub struct DiffOptions {
+    pub run: RunOptions,
+    /// Number of generated inputs per part
+    pub cases: usize,
+    pub seed: u64,
+}
+
+/// Parse the command line arguments (without the program name)
+pub fn parse_args<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+
+    let command = match args.peek() {
+        Some(arg) if !arg.starts_with('-') => args.next().unwrap(),
+        _ => String::from("run"),
+    };
+
+    match command.as_str() {
+        "run" => parse_run(args),
+        "verify" => parse_verify(args),
+        "bench" => parse_bench(args),
+        "check" => parse_check(args),
+        "new-day" => parse_new_day(args),
+        "fetch" => parse_fetch(args),
+        "submit" => parse_submit(args),
+        "watch" => parse_watch(args),
+        "report" => parse_report(args),
+        "repl" => parse_repl(args),
+        "generate" => parse_generate(args),
+        "diff" => parse_diff(args),
+        "list" => expect_end(args, Command::List),
+        "help" => Ok(Command::Help),
+        _ => Err(format!("unknown command '{command}'")),
+    }
+}
+
+fn parse_run<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = RunOptions::default();
+    while let Some(arg) = args.next() {
+        if run_option(&mut options, &arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "-e" | "--examples" => options.examples = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    if options.input.is_some() && options.examples {
+        return Err(String::from("--input can not be combined with --examples"));
+    }
+    if options.format != Format::Text && options.examples {
+        return Err(String::from("--format can not be combined with --examples"));
+    }
+    if options.alloc && options.examples {
+        return Err(String::from("--alloc can not be combined with --examples"));
+    }
+
+    Ok(Command::Run(validate(options)?))
+}
+
+fn parse_verify<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = VerifyOptions {
+        run: RunOptions::default(),
+        answers: String::from(answers::DEFAULT_PATH),
+        accept: false,
+    };
+    while let Some(arg) = args.next() {
+        if run_option(&mut options.run, &arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--answers" => options.answers = value(&arg, args.next())?,
+            "--accept" => options.accept = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    options.run = validate(options.run)?;
+    Ok(Command::Verify(options))
+}
+
+fn parse_bench<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = BenchOptions {
+        run: RunOptions::default(),
+        runs: 10,
+        warmup: 2,
+        save: None,
+        baseline: None,
+    };
+    while let Some(arg) = args.next() {
+        if run_option(&mut options.run, &arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--runs" => options.runs = parse_count(&arg, &value(&arg, args.next())?, 1)?,
+            "--warmup" => options.warmup = parse_count(&arg, &value(&arg, args.next())?, 0)?,
+            "--save" => options.save = Some(value(&arg, args.next())?),
+            "--baseline" => options.baseline = Some(value(&arg, args.next())?),
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    unsupported(&options.run, "bench", &["--format", "--alloc", "--jobs"])?;
+    options.run = validate(options.run)?;
+    Ok(Command::Bench(options))
+}
+
+fn parse_new_day<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let day = parse_day(&value("new-day", args.next())?)?;
+    let mut year = None;
+    let mut title = String::from("TODO");
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-y" | "--year" => year = Some(parse_year(&value(&arg, args.next())?)?),
+            "-t" | "--title" => title = value(&arg, args.next())?,
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    Ok(Command::NewDay { year, day, title })
+}
+
+fn parse_submit<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = SubmitOptions {
+        run: RunOptions::default(),
+        answer: None,
+        answers: String::from(answers::DEFAULT_PATH),
+    };
+    while let Some(arg) = args.next() {
+        if run_option(&mut options.run, &arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--answer" => options.answer = Some(value(&arg, args.next())?),
+            "--answers" => options.answers = value(&arg, args.next())?,
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    if options.run.days.len() != 1 || options.run.part.is_none() {
+        return Err(String::from(
+            "submit requires exactly one --day and a --part",
+        ));
+    }
+    unsupported(&options.run, "submit", &["--format", "--alloc", "--jobs"])?;
+    options.run = validate(options.run)?;
+    Ok(Command::Submit(options))
+}
+
+fn parse_watch<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = WatchOptions {
+        run: RunOptions::default(),
+        example: None,
+        interval: Duration::from_millis(500),
+    };
+    while let Some(arg) = args.next() {
+        if run_option(&mut options.run, &arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--example" => options.example = Some(value(&arg, args.next())?),
+            "--interval" => {
+                let millis = parse_count(&arg, &value(&arg, args.next())?, 1)?;
+                options.interval = Duration::from_millis(millis as u64);
+            }
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    if options.run.days.len() != 1 {
+        return Err(String::from("watch requires exactly one --day"));
+    }
+    unsupported(&options.run, "watch", &["--format", "--jobs"])?;
+    options.run = validate(options.run)?;
+    Ok(Command::Watch(options))
+}
+
+fn parse_check<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = RunOptions::default();
+    while let Some(arg) = args.next() {
+        if run_option(&mut options, &arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    unsupported(&options, "check", &["--format", "--alloc", "--jobs"])?;
+    Ok(Command::Check(validate(options)?))
+}
+
+fn parse_report<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = ReportOptions {
+        run: RunOptions::default(),
+        answers: String::from(answers::DEFAULT_PATH),
+        no_run: false,
+        output: None,
+    };
+    while let Some(arg) = args.next() {
+        if run_option(&mut options.run, &arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--answers" => options.answers = value(&arg, args.next())?,
+            "--no-run" => options.no_run = true,
+            "-o" | "--output" => options.output = Some(value(&arg, args.next())?),
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    unsupported(&options.run, "report", &["--format", "--alloc"])?;
+    options.run = validate(options.run)?;
+    Ok(Command::Report(options))
+}
+
+fn parse_repl<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = RunOptions::default();
+    while let Some(arg) = args.next() {
+        if run_option(&mut options, &arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    if options.days.len() != 1 {
+        return Err(String::from("repl requires exactly one --day"));
+    }
+    unsupported(&options, "repl", &["--format", "--alloc", "--jobs"])?;
+    Ok(Command::Repl(validate(options)?))
+}
+
+fn parse_generate<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = GenerateOptions {
+        run: RunOptions::default(),
+        size: 100,
+        seed: prop::DEFAULT_SEED,
+        output: None,
+    };
+    while let Some(arg) = args.next() {
+        if run_option(&mut options.run, &arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--size" => options.size = parse_count(&arg, &value(&arg, args.next())?, 1)?,
+            "--seed" => options.seed = parse_seed(&value(&arg, args.next())?)?,
+            "-o" | "--output" => options.output = Some(value(&arg, args.next())?),
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    if options.run.days.len() != 1 {
+        return Err(String::from("generate requires exactly one --day"));
+    }
+    unsupported(&options.run, "generate", &["--format", "--alloc", "--jobs"])?;
+    options.run = validate(options.run)?;
+    Ok(Command::Generate(options))
+}
+
+fn parse_diff<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = DiffOptions {
+        run: RunOptions::default(),
+        cases: 2000,
+        seed: prop::DEFAULT_SEED,
+    };
+    while let Some(arg) = args.next() {
+        if run_option(&mut options.run, &arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--cases" => options.cases = parse_count(&arg, &value(&arg, args.next())?, 1)?,
+            "--seed" => options.seed = parse_seed(&value(&arg, args.next())?)?,
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    if options.run.input.is_some() {
+        return Err(String::from("--input is not supported by diff"));
+    }
+    unsupported(&options.run, "diff", &["--format", "--alloc", "--jobs"])?;
+    Ok(Command::Diff(options))
+}
+
+fn parse_fetch<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut years = vec![];
+    let mut days = vec![];
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-y" | "--year" | "--years" => years = parse_years(&value(&arg, args.next())?)?,
+            "-d" | "--day" | "--days" => days = parse_days(&value(&arg, args.next())?)?,
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    if days.is_empty() {
+        return Err(String::from("fetch requires --day"));
+    }
+    Ok(Command::Fetch { years, days })
+}
+
+/// Apply an option shared by all commands running days,
+/// returns false if `arg` is not one of them
+fn run_option<I>(options: &mut RunOptions, arg: &str, args: &mut I) -> Result<bool, String>
+where
+    I: Iterator<Item = String>,
+{
+    match arg {
+        "-y" | "--year" | "--years" => options.years = parse_years(&value(arg, args.next())?)?,
+        "-d" | "--day" | "--days" => options.days = parse_days(&value(arg, args.next())?)?,
+        "-p" | "--part" => options.part = Some(parse_part(&value(arg, args.next())?)?),
+        "-i" | "--input" => options.input = Some(value(arg, args.next())?),
+        "-j" | "--jobs" => options.jobs = parse_count(arg, &value(arg, args.next())?, 0)?,
+        "-f" | "--format" => options.format = parse_format(&value(arg, args.next())?)?,
+        "-a" | "--alloc" => options.alloc = true,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Fails if one of the given shared `flags` was passed to a command that ignores it
+fn unsupported(options: &RunOptions, command: &str, flags: &[&str]) -> Result<(), String> {
+    for flag in flags {
+        let set = match *flag {
+            "--format" => options.format != Format::Text,
+            "--alloc" => options.alloc,
+            "--jobs" => options.jobs != 1,
+            _ => false,
+        };
+        if set {
+            return Err(format!("{flag} is not supported by {command}"));
+        }
+    }
+    Ok(())
+}
+
+fn validate(options: RunOptions) -> Result<RunOptions, String> {
+    if options.input.is_some() && options.days.len() != 1 {
+        return Err(String::from("--input requires exactly one --day"));
+    }
+    Ok(options)
+}
+
+fn expect_end<I>(mut args: I, command: Command) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    match args.next() {
+        Some(arg) => Err(format!("unexpected argument '{arg}'")),
+        None => Ok(command),
+    }
+}
+
+fn value(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or(format!("missing value for {flag}"))
+}
+
+/// Parse a day selection like "7", "1-3" or "1,4-6"
+fn parse_days(str: &str) -> Result<Vec<u32>, String> {
+    parse_selection(str, parse_day)
+}
+
+/// Parse a year selection like "2022" or "2015-2017"
+fn parse_years(str: &str) -> Result<Vec<u32>, String> {
+    parse_selection(str, parse_year)
+}
+
+fn parse_selection<F>(str: &str, parse: F) -> Result<Vec<u32>, String>
+where
+    F: Fn(&str) -> Result<u32, String>,
+{
+    let mut selection = vec![];
+    for item in str.split(',') {
+        let (start, end) = match item.split_once('-') {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None => (parse(item)?, parse(item)?),
+        };
+        if start > end {
+            return Err(format!("invalid range '{item}'"));
+        }
+        selection.extend(start..=end);
+    }
+    selection.sort();
+    selection.dedup();
+    Ok(selection)
+}
+
+fn parse_year(str: &str) -> Result<u32, String> {
+    // the first event took place in 2015, there are no puzzles of future years
+    let last = current_year();
+    match str.trim().parse::<u32>() {
+        Ok(year) if (2015..=last).contains(&year) => Ok(year),
+        _ => Err(format!(
+            "invalid year '{str}', expected a year from 2015 to {last}"
+        )),
+    }
+}
+
+/// Year of the system clock, close enough around new year for selecting events
+fn current_year() -> u32 {
+    let secs = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    // average length of a gregorian year
+    1970 + (secs / 31_556_952) as u32
+}
+
+fn parse_day(str: &str) -> Result<u32, String> {
+    match str.trim().parse::<u32>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "invalid day '{str}', expected a number from 1 to 25"
+        )),
+    }
+}
+
+fn parse_count(flag: &str, str: &str, min: usize) -> Result<usize, String> {
+    match str.parse::<usize>() {
+        Ok(count) if count >= min => Ok(count),
+        _ => Err(format!(
+            "invalid value '{str}' for {flag}, expected a number >= {min}"
+        )),
+    }
+}
+
+fn parse_seed(str: &str) -> Result<u64, String> {
+    str.parse::<u64>()
+        .map_err(|_| format!("invalid seed '{str}', expected a number"))
+}
+
+fn parse_part(str: &str) -> Result<Part, String> {
+    match str {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("invalid part '{str}', expected 1 or 2")),
+    }
+}
+
+fn parse_format(str: &str) -> Result<Format, String> {
+    match str {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        _ => Err(format!(
+            "invalid format '{str}', expected text, json or csv"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, String> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    fn error(args: &str) -> String {
+        parse(args).err().unwrap()
+    }
+
+    #[test]
+    fn parses_commands_and_options() {
+        let Ok(Command::Run(options)) = parse("-y 2022 -d 1,4-6,5 -p 2 -j 0 -f json -a") else {
+            panic!("expected run");
+        };
+        assert_eq!(options.years, [2022]);
+        assert_eq!(options.days, [1, 4, 5, 6]);
+        assert!(options.part == Some(Part::Two));
+        assert_eq!(options.jobs, 0);
+        assert!(options.format == Format::Json && options.alloc);
+
+        let Ok(Command::Bench(options)) = parse("bench -d 3 --runs 5 --warmup 0") else {
+            panic!("expected bench");
+        };
+        assert_eq!((options.runs, options.warmup), (5, 0));
+        assert_eq!(options.run.parts().len(), 2);
+        assert!(matches!(
+            parse("new-day 9 --year 2022"),
+            Ok(Command::NewDay {
+                year: Some(2022),
+                day: 9,
+                ..
+            })
+        ));
+        assert!(matches!(parse("list"), Ok(Command::List)));
+        assert!(matches!(parse("run --help"), Ok(Command::Help)));
+    }
+
+    #[test]
+    fn selects_ranges_of_days_and_years() {
+        assert_eq!(parse_days("7").unwrap(), [7]);
+        assert_eq!(parse_days("3-1").err().unwrap(), "invalid range '3-1'");
+        assert_eq!(parse_years("2015-2017,2016").unwrap(), [2015, 2016, 2017]);
+        assert_eq!(
+            parse_days("1-26").err().unwrap(),
+            "invalid day '26', expected a number from 1 to 25"
+        );
+        // years are bounded like days, a huge range is rejected instead of expanded
+        let last = current_year();
+        assert!(last >= 2022);
+        assert_eq!(
+            parse_years("2015-4000000000").err().unwrap(),
+            format!("invalid year '4000000000', expected a year from 2015 to {last}")
+        );
+        assert!(parse_years("2014").is_err());
+    }
+
+    #[test]
+    fn reports_invalid_arguments() {
+        assert_eq!(error("frobnicate"), "unknown command 'frobnicate'");
+        assert_eq!(error("run --day"), "missing value for --day");
+        assert_eq!(error("run -x"), "unexpected argument '-x'");
+        assert_eq!(error("run -i a.txt"), "--input requires exactly one --day");
+        assert_eq!(error("list -d 1"), "unexpected argument '-d'");
+        assert_eq!(
+            error("run -f xml"),
+            "invalid format 'xml', expected text, json or csv"
+        );
+        assert_eq!(error("repl"), "repl requires exactly one --day");
+        assert_eq!(
+            error("submit -d 1"),
+            "submit requires exactly one --day and a --part"
+        );
+    }
+
+    #[test]
+    fn rejects_shared_options_a_command_ignores() {
+        assert_eq!(error("check -f json"), "--format is not supported by check");
+        assert_eq!(error("watch -d 1 -j 4"), "--jobs is not supported by watch");
+        assert!(matches!(parse("watch -d 1 -a"), Ok(Command::Watch(_))));
+        assert_eq!(error("repl -d 5 -a"), "--alloc is not supported by repl");
+        assert_eq!(
+            error("generate -d 1 -f csv"),
+            "--format is not supported by generate"
+        );
+        assert_eq!(error("diff -j 2"), "--jobs is not supported by diff");
+        assert_eq!(error("bench -a"), "--alloc is not supported by bench");
+        assert!(matches!(
+            parse("verify -j 2 -a -f csv"),
+            Ok(Command::Verify(_))
+        ));
+    }
+}
@@ -0,0 +1,244 @@
+use std::{
+    env, fs,
+    io::{self, ErrorKind},
+    path::Path,
+    time::Duration,
+};
+
+use crate::{
+    answers::Verdict,
+    error::{Error, Result},
+    http,
+    solution::Part,
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Response of the website to a submitted answer
+#[derive(PartialEq, Eq, Debug)]
+pub enum Submission {
+    Answered(Verdict),
+    /// The previous answer was submitted too recently, `wait` until the next one
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part is already solved or the first part has to be solved first
+    WrongLevel,
+}
+
+/// Client for the Advent of Code website, authenticated by a session token
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Client for the website or the server in `AOC_BASE_URL`,
+    /// using the session token in `AOC_SESSION` or else the one in the session file
+    pub fn from_env() -> Result<Client> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or(String::from(DEFAULT_BASE_URL));
+        if let Ok(session) = env::var("AOC_SESSION") {
+            return Ok(Client::new(&base_url, &session));
+        }
+
+        let path = session_file();
+        match fs::read_to_string(&path) {
+            Ok(session) if !session.trim().is_empty() => Ok(Client::new(&base_url, &session)),
+            Ok(_) => Err(no_session(&path, ErrorKind::InvalidData)),
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                Err(no_session(&path, ErrorKind::NotFound))
+            }
+            Err(source) => Err(Error::Io { path, source }),
+        }
+    }
+
+    /// Puzzle input of a day
+    pub fn input(&self, year: u32, day: u32) -> Result<String> {
+        self.request("GET", &format!("/{year}/day/{day}/input"), None)
+    }
+
+    /// Submit the answer of a part
+    pub fn submit(&self, year: u32, day: u32, part: Part, answer: &str) -> Result<Submission> {
+        let path = format!("/{year}/day/{day}/answer");
+        let body = format!("level={part}&answer={}", url_encode(answer));
+        let page = self.request("POST", &path, Some(&body))?;
+        parse_submission(&page).ok_or_else(|| Error::Io {
+            path: format!("{}{path}", self.base_url),
+            source: io::Error::new(ErrorKind::InvalidData, "unexpected response page"),
+        })
+    }
+
+    /// Send a request with the session cookie, returns the body of a successful response
+    fn request(&self, method: &str, path: &str, body: Option<&str>) -> Result<String> {
+        let url = format!("{}{path}", self.base_url);
+        let cookie = format!("session={}", self.session);
+        let mut headers = vec![
+            ("Cookie", cookie.as_str()),
+            ("User-Agent", "github.com/foolish314159/advent-of-code-rust"),
+        ];
+        if body.is_some() {
+            headers.push(("Content-Type", "application/x-www-form-urlencoded"));
+        }
+
+        let io_error = |source| Error::Io {
+            path: url.clone(),
+            source,
+        };
+        let response = http::request(method, &url, &headers, body).map_err(io_error)?;
+        match response.status {
+            200 => Ok(response.body),
+            status => Err(io_error(io::Error::other(format!(
+                "HTTP status {status}: {}",
+                response.body.lines().next().unwrap_or("").trim()
+            )))),
+        }
+    }
+}
+
+fn parse_submission(page: &str) -> Option<Submission> {
+    let verdict = if page.contains("That's the right answer") {
+        Verdict::Correct
+    } else if page.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if page.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if page.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if page.contains("You gave an answer too recently") {
+        return Some(Submission::RateLimited {
+            wait: parse_wait(page),
+        });
+    } else if page.contains("You don't seem to be solving the right level") {
+        return Some(Submission::WrongLevel);
+    } else {
+        return None;
+    };
+    Some(Submission::Answered(verdict))
+}
+
+/// Wait time of a message like "You have 1m 5s left to wait"
+fn parse_wait(page: &str) -> Option<Duration> {
+    let (_, rest) = page.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .try_fold(Duration::ZERO, |total, amount| {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let seconds = match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+            Some(total + Duration::from_secs(number.parse::<u64>().ok()? * seconds))
+        })
+}
+
+fn url_encode(str: &str) -> String {
+    str.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Location of the session token if it is not set in the environment
+pub fn session_file() -> String {
+    let home = env::var("HOME").unwrap_or(String::from("."));
+    format!("{home}/.config/adventofcode/session")
+}
+
+fn no_session(path: &str, kind: ErrorKind) -> Error {
+    Error::Io {
+        path: path.to_string(),
+        source: io::Error::new(
+            kind,
+            "no session token, set AOC_SESSION or write the token to this file",
+        ),
+    }
+}
+
+/// Download the input of a day into its default location below `root`,
+/// unless a non empty input file already exists there.
+///
+/// Returns the path of the input file and whether it was downloaded.
+pub fn fetch_input(client: &Client, root: &Path, year: u32, day: u32) -> Result<(String, bool)> {
+    let path = root.join(crate::input_file(year, day));
+    let display = path.display().to_string();
+    let io_error = |source| Error::Io {
+        path: display.clone(),
+        source,
+    };
+
+    // new-day creates an empty input file, that one still needs to be filled
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok((display, false));
+    }
+
+    let input = client.input(year, day)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(&path, input).map_err(io_error)?;
+    Ok((display, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::stub;
+
+    #[test]
+    fn fetch_downloads_only_once() {
+        let root = env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        // a second download would fail since the stub only answers once
+        let (url, server) = stub::serve(vec![(200, String::from("1000\n2000\n"))]);
+        let client = Client::new(&url, "secret");
+
+        let (path, downloaded) = fetch_input(&client, &root, 2022, 1).unwrap();
+        assert!(downloaded);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        assert_eq!(fetch_input(&client, &root, 2022, 1).unwrap(), (path, false));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=secret\r\n"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn submit_parses_response_page() {
+        let pages = [
+            "<article><p>That's not the right answer; your answer is too high.  If you're stuck, ...",
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. ...",
+            "<article><p>That's the right answer!  You are one gold star closer to ...",
+        ];
+        let (url, server) = stub::serve(pages.iter().map(|page| (200, page.to_string())).collect());
+        let client = Client::new(&url, "secret");
+
+        let submit = |answer| client.submit(2022, 5, Part::Two, answer).unwrap();
+        assert_eq!(submit("ZZZ"), Submission::Answered(Verdict::TooHigh));
+        assert_eq!(
+            submit("MCD"),
+            Submission::RateLimited {
+                wait: Some(Duration::from_secs(65))
+            }
+        );
+        assert_eq!(submit("M C&D"), Submission::Answered(Verdict::Correct));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/5/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=ZZZ"));
+        assert!(requests[2].ends_with("\r\n\r\nlevel=2&answer=M%20C%26D"));
+    }
+}
@@ -0,0 +1,429 @@
+use crate::{
+    answers::{Answers, Verdict},
+    client::Submission,
+    error::{read_input, Result},
+    output::{self, Record, Status},
+    prop::Gen,
+    repl::Session,
+    runner::{self, DayResult},
+    solution::{Part, Runner},
+    REGISTRY,
+};
+
+/// Registered days of the selected years and days, empty selections select all.
+/// Fails if a selected year or day isn't implemented, or if an `--input` file
+/// is given and more than one day matches.
+pub fn select_days(
+    years: &[u32],
+    days: &[u32],
+    input: bool,
+) -> std::result::Result<Vec<&'static dyn Runner>, String> {
+    let includes = |day: &dyn Runner| {
+        (years.is_empty() || years.contains(&day.year()))
+            && (days.is_empty() || days.contains(&day.day()))
+    };
+
+    if let Some(missing) = years
+        .iter()
+        .find(|year| !REGISTRY.iter().any(|d| d.year() == **year))
+    {
+        return Err(format!("year {missing} has no days implemented yet"));
+    }
+    if let Some(missing) = days
+        .iter()
+        .find(|day| !REGISTRY.iter().any(|d| d.day() == **day && includes(*d)))
+    {
+        return Err(format!("day {missing} is not implemented yet"));
+    }
+
+    let selected = REGISTRY
+        .iter()
+        .filter(|d| includes(**d))
+        .copied()
+        .collect::<Vec<_>>();
+    if input && selected.len() != 1 {
+        return Err(format!(
+            "--input matches {} days, select a single --year",
+            selected.len()
+        ));
+    }
+    Ok(selected)
+}
+
+/// The only registered day of the selection, see [`select_days`]
+pub fn select_day(years: &[u32], days: &[u32]) -> std::result::Result<&'static dyn Runner, String> {
+    match select_days(years, days, false)?[..] {
+        [day] => Ok(day),
+        ref selected => Err(format!(
+            "--day matches {} days, select a single --year",
+            selected.len()
+        )),
+    }
+}
+
+/// Latest year with registered days, the default of commands taking a year
+pub fn latest_year() -> Option<u32> {
+    REGISTRY.iter().map(|d| d.year()).max()
+}
+
+/// The selected years, or the [`latest_year`] if none is selected
+pub fn years_or_latest(years: &[u32]) -> std::result::Result<Vec<u32>, String> {
+    match years {
+        [] => latest_year()
+            .map(|year| vec![year])
+            .ok_or(String::from("no days registered yet, select a --year")),
+        years => Ok(years.to_vec()),
+    }
+}
+
+/// Solve the parts of the days, one record per part with status ok or error
+pub fn run<F>(
+    days: &[&'static dyn Runner],
+    parts: &[Part],
+    input_file: F,
+    jobs: usize,
+) -> Vec<Record>
+where
+    F: Fn(&dyn Runner) -> String,
+{
+    runner::execute(days, parts, input_file, jobs)
+        .iter()
+        .flat_map(|result| output::records(result, parts))
+        .collect()
+}
+
+/// Solve the parts of the days and compare the answers with the known ones,
+/// see [`check_answers`]
+pub fn verify<F>(
+    days: &[&'static dyn Runner],
+    parts: &[Part],
+    input_file: F,
+    jobs: usize,
+    answers: &mut Answers,
+    accept: bool,
+) -> Vec<Record>
+where
+    F: Fn(&dyn Runner) -> String,
+{
+    let results = runner::execute(days, parts, input_file, jobs);
+    check_answers(&results, parts, answers, accept)
+}
+
+/// Records of the results with the known answers as expected ones,
+/// status pass, fail or new if a part was solved and error otherwise.
+/// New answers are recorded if they should be accepted.
+pub fn check_answers(
+    results: &[DayResult],
+    parts: &[Part],
+    answers: &mut Answers,
+    accept: bool,
+) -> Vec<Record> {
+    let mut records = vec![];
+    for result in results {
+        let hash = result.input_hash.unwrap_or_default();
+        for mut r in output::records(result, parts) {
+            if let Some(answer) = &r.answer {
+                r.expected = answers
+                    .get_hashed(r.year, r.day, r.part, hash)
+                    .map(String::from);
+                let rejection = answers.rejection_hashed(r.year, r.day, r.part, hash, answer);
+                r.status = match (&r.expected, rejection) {
+                    (Some(known), _) if known == answer => Status::Pass,
+                    (Some(_), _) => Status::Fail,
+                    // a guess known to be wrong is never accepted
+                    (None, Some(reason)) => {
+                        r.detail = Some(reason);
+                        Status::Fail
+                    }
+                    (None, None) => {
+                        if accept {
+                            answers.record_hashed(r.year, r.day, r.part, hash, answer);
+                        }
+                        Status::New
+                    }
+                };
+            }
+            records.push(r);
+        }
+    }
+    records
+}
+
+/// Number of verified records by status, errors count as failed
+pub struct Tally {
+    pub passed: usize,
+    pub failed: usize,
+    pub new: usize,
+}
+
+impl Tally {
+    pub fn of(records: &[Record]) -> Tally {
+        let count = |status| records.iter().filter(|r| r.status == status).count();
+        let (passed, new) = (count(Status::Pass), count(Status::New));
+        Tally {
+            passed,
+            failed: records.len() - passed - new,
+            new,
+        }
+    }
+}
+
+/// Rendered diagnostics of every problem of a day's input file,
+/// or of the error reading it
+pub fn check_input(day: &dyn Runner, filename: &str) -> Vec<String> {
+    match read_input(filename) {
+        Ok(input) => day
+            .check(&input)
+            .iter()
+            .map(|problem| problem.diagnostic(filename, &input))
+            .collect(),
+        Err(err) => vec![err.diagnostic(filename, "")],
+    }
+}
+
+/// REPL session exploring the input file of a day
+pub fn open_session(
+    day: &dyn Runner,
+    filename: &str,
+) -> std::result::Result<Box<dyn Session>, String> {
+    let Some(open) = day.session() else {
+        return Err(format!(
+            "day {} of {} has no REPL commands yet",
+            day.day(),
+            day.year()
+        ));
+    };
+    read_input(filename)
+        .and_then(|input| open(&input))
+        .map_err(|err| err.in_file(filename))
+}
+
+/// Random input of a day, lists in it have `size` elements on average
+pub fn generate_input(
+    day: &dyn Runner,
+    seed: u64,
+    size: usize,
+) -> std::result::Result<String, String> {
+    match day.generator() {
+        Some(generate) => Ok(generate(&mut Gen::new(seed, size))),
+        None => Err(format!(
+            "day {} of {} has no input generator yet",
+            day.day(),
+            day.year()
+        )),
+    }
+}
+
+/// The input of a day and the answer to submit for a part,
+/// which is solved unless an `answer` is given
+pub fn submission(
+    day: &dyn Runner,
+    part: Part,
+    filename: &str,
+    answer: Option<&str>,
+) -> Result<(String, String)> {
+    let input = read_input(filename)?;
+    let answer = match answer {
+        Some(answer) => answer.to_string(),
+        None => day.solve(day.parse(&input)?.as_ref(), part)?,
+    };
+    Ok((input, answer))
+}
+
+/// What happened to an answer passed to [`submit`]
+pub enum SubmitOutcome {
+    /// Not submitted, the recorded answers tell why it's pointless
+    Rejected(String),
+    /// Submitted and answered with this response
+    Submitted(Submission),
+}
+
+/// Submit the answer with `send` unless the recorded answers reject it,
+/// then record and save the verdict if there is one
+pub fn submit<F>(
+    answers: &mut Answers,
+    day: &dyn Runner,
+    part: Part,
+    input: &str,
+    answer: &str,
+    send: F,
+) -> Result<SubmitOutcome>
+where
+    F: FnOnce(&str) -> Result<Submission>,
+{
+    let (year, number) = (day.year(), day.day());
+    if let Some(reason) = answers.rejection(year, number, part, input, answer) {
+        return Ok(SubmitOutcome::Rejected(reason));
+    }
+
+    let submission = send(answer)?;
+    if let Submission::Answered(verdict) = submission {
+        answers.record_guess(year, number, part, input, answer, verdict);
+        answers.save()?;
+    }
+    Ok(SubmitOutcome::Submitted(submission))
+}
+
+/// Solve a part, unless an `answer` is given, and [`submit`] it with `send`,
+/// recording the verdict in the answers file at `answers_path`.
+/// Returns the submitted answer and what happened to it.
+pub fn submit_part<F>(
+    day: &dyn Runner,
+    part: Part,
+    filename: &str,
+    answer: Option<&str>,
+    answers_path: &str,
+    send: F,
+) -> std::result::Result<(String, SubmitOutcome), String>
+where
+    F: FnOnce(&str) -> Result<Submission>,
+{
+    let (input, answer) =
+        submission(day, part, filename, answer).map_err(|err| err.in_file(filename))?;
+    let mut answers = Answers::load(answers_path).map_err(|err| err.in_file(answers_path))?;
+    let outcome =
+        submit(&mut answers, day, part, &input, &answer, send).map_err(|err| err.to_string())?;
+    Ok((answer, outcome))
+}
+
+impl SubmitOutcome {
+    /// Whether the answer was submitted and correct
+    pub fn is_correct(&self) -> bool {
+        matches!(
+            self,
+            SubmitOutcome::Submitted(Submission::Answered(Verdict::Correct))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::*;
+    use crate::{runner, y2022::day01::Day1};
+
+    const INPUT: &str = "1\n2\n\n4\n";
+
+    fn answers_file(name: &str) -> String {
+        let path = env::temp_dir().join(format!("aoc-{name}-{}.txt", process::id()));
+        let _ = fs::remove_file(&path);
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn selects_registered_days() {
+        assert_eq!(select_days(&[], &[], false).unwrap().len(), REGISTRY.len());
+        assert_eq!(
+            select_days(&[2022], &[1, 3], true).err().unwrap(),
+            "--input matches 2 days, select a single --year"
+        );
+        assert_eq!(
+            select_days(&[2015], &[], false).err().unwrap(),
+            "year 2015 has no days implemented yet"
+        );
+        assert_eq!(
+            select_days(&[], &[25], false).err().unwrap(),
+            "day 25 is not implemented yet"
+        );
+        assert_eq!(select_day(&[], &[3]).unwrap().day(), 3);
+        assert!(select_day(&[], &[]).is_err());
+        assert_eq!(years_or_latest(&[]).unwrap(), [2022]);
+        assert_eq!(years_or_latest(&[2015, 2016]).unwrap(), [2015, 2016]);
+    }
+
+    #[test]
+    fn opens_sessions_and_generates_inputs_of_days_that_can() {
+        let day = |n| select_day(&[], &[n]).unwrap();
+        let path = answers_file("session");
+        fs::write(&path, "30373\n25512\n").unwrap();
+        let session = open_session(day(8), &path);
+        fs::remove_file(&path).unwrap();
+        assert!(session.is_ok());
+        assert_eq!(
+            open_session(day(1), &path).err().unwrap(),
+            "day 1 of 2022 has no REPL commands yet"
+        );
+        assert!(open_session(day(8), "does/not/exist.txt")
+            .err()
+            .unwrap()
+            .starts_with("does/not/exist.txt: "));
+
+        let input = generate_input(day(2), 7, 10).unwrap();
+        assert_eq!(generate_input(day(2), 7, 10).unwrap(), input);
+        assert!(day(2).parse(&input).is_ok());
+    }
+
+    #[test]
+    fn classifies_and_accepts_answers() {
+        let path = answers_file("check-answers");
+        fs::write(&path, "").unwrap();
+        let mut answers = Answers::load(&path).unwrap();
+        answers.record(2022, 1, Part::One, INPUT, "4");
+        answers.record(2022, 1, Part::Two, INPUT, "6");
+        let results = || {
+            fs::write(&path, INPUT).unwrap();
+            runner::execute(&[&Day1], &[Part::One, Part::Two], |_| path.clone(), 1)
+        };
+
+        let records = check_answers(&results(), &[Part::One, Part::Two], &mut answers, false);
+        let status = records.iter().map(|r| r.status).collect::<Vec<_>>();
+        assert_eq!(status, [Status::Pass, Status::Fail]);
+        let tally = Tally::of(&records);
+        assert_eq!((tally.passed, tally.failed, tally.new), (1, 1, 0));
+        assert_eq!(records[1].expected.as_deref(), Some("6"));
+
+        let mut empty = Answers::load(&answers_file("empty")).unwrap();
+        let records = check_answers(&results(), &[Part::One], &mut empty, true);
+        assert_eq!(records[0].status, Status::New);
+        assert_eq!(empty.get(2022, 1, Part::One, INPUT), Some("4"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn wrong_guesses_fail_and_are_not_accepted() {
+        let path = answers_file("check-guesses");
+        let mut answers = Answers::load(&path).unwrap();
+        answers.record_guess(2022, 1, Part::One, INPUT, "4", Verdict::Wrong);
+        answers.record_guess(2022, 1, Part::Two, INPUT, "100", Verdict::TooHigh);
+        fs::write(&path, INPUT).unwrap();
+        let results = runner::execute(&[&Day1], &[Part::One, Part::Two], |_| path.clone(), 1);
+        fs::remove_file(&path).unwrap();
+
+        let records = check_answers(&results, &[Part::One, Part::Two], &mut answers, true);
+        let status = records.iter().map(|r| r.status).collect::<Vec<_>>();
+        assert_eq!(status, [Status::Fail, Status::New]);
+        assert_eq!(records[0].expected, None);
+        assert_eq!(records[0].detail.as_deref(), Some("4 was wrong"));
+        assert_eq!(answers.get(2022, 1, Part::One, INPUT), None);
+        assert_eq!(answers.get(2022, 1, Part::Two, INPUT), Some("7"));
+    }
+
+    #[test]
+    fn submits_unless_rejected_and_records_verdicts() {
+        let path = answers_file("submit");
+        let mut answers = Answers::load(&path).unwrap();
+
+        let outcome = submit(&mut answers, &Day1, Part::One, INPUT, "3", |_| {
+            Ok(Submission::Answered(Verdict::TooLow))
+        });
+        assert!(!outcome.unwrap().is_correct());
+
+        // too low before, so it isn't sent again
+        let outcome = submit(&mut answers, &Day1, Part::One, INPUT, "2", |_| {
+            panic!("rejected answers must not be submitted")
+        });
+        assert!(
+            matches!(outcome, Ok(SubmitOutcome::Rejected(reason)) if reason == "3 was too low")
+        );
+
+        let outcome = submit(&mut answers, &Day1, Part::One, INPUT, "4", |answer| {
+            assert_eq!(answer, "4");
+            Ok(Submission::Answered(Verdict::Correct))
+        });
+        assert!(outcome.unwrap().is_correct());
+        let saved = Answers::load(&path).unwrap();
+        assert_eq!(saved.get(2022, 1, Part::One, INPUT), Some("4"));
+        fs::remove_file(&path).unwrap();
+    }
+}
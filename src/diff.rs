@@ -0,0 +1,286 @@
+use crate::{
+    error::Result,
+    prop::{self, Failure, Gen},
+    solution::{self, Part, Solution},
+    y2022::{day01, day04, day06, day08},
+};
+
+/// Implementation of a part, from the puzzle input to the answer
+pub type Solve = fn(&str) -> Result<String>;
+
+/// Implementations of a part that have to give the same answers.
+/// The first one is the simple reference the optimised ones are compared with.
+pub struct Implementations {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    pub solvers: &'static [(&'static str, Solve)],
+}
+
+/// Parts with more than one implementation by year, day and part.
+/// Keeping a reference next to an optimised solver only requires a new entry here.
+pub static IMPLEMENTATIONS: &[Implementations] = &[
+    Implementations {
+        year: 2022,
+        day: 1,
+        part: Part::One,
+        solvers: &[
+            ("sorted", |input| {
+                day01::Day1.part1(&day01::Day1.parse(input)?)
+            }),
+            ("streaming", |input| {
+                Ok(day01::top_n_elves_str(input, 1)?.to_string())
+            }),
+        ],
+    },
+    Implementations {
+        year: 2022,
+        day: 1,
+        part: Part::Two,
+        solvers: &[
+            ("sorted", |input| {
+                day01::Day1.part2(&day01::Day1.parse(input)?)
+            }),
+            ("streaming", |input| {
+                Ok(day01::top_n_elves_str(input, 3)?.to_string())
+            }),
+        ],
+    },
+    Implementations {
+        year: 2022,
+        day: 4,
+        part: Part::One,
+        solvers: &[
+            ("parsed", |input| {
+                day04::Day4.part1(&day04::Day4.parse(input)?)
+            }),
+            ("streaming", |input| {
+                Ok(day04::sum_full_overlaps_str(input)?.to_string())
+            }),
+        ],
+    },
+    Implementations {
+        year: 2022,
+        day: 4,
+        part: Part::Two,
+        solvers: &[
+            ("parsed", |input| {
+                day04::Day4.part2(&day04::Day4.parse(input)?)
+            }),
+            ("streaming", |input| {
+                Ok(day04::sum_partial_overlaps_str(input)?.to_string())
+            }),
+        ],
+    },
+    Implementations {
+        year: 2022,
+        day: 6,
+        part: Part::One,
+        solvers: &[
+            ("window", |input| {
+                Ok(day06::CharMarker::<4>::find_index_after_marker(input)?.to_string())
+            }),
+            ("last-seen", |input| {
+                Ok(day06::chars_before_marker_str::<4>(input)?.to_string())
+            }),
+        ],
+    },
+    Implementations {
+        year: 2022,
+        day: 6,
+        part: Part::Two,
+        solvers: &[
+            ("window", |input| {
+                Ok(day06::CharMarker::<14>::find_index_after_marker(input)?.to_string())
+            }),
+            ("last-seen", |input| {
+                Ok(day06::chars_before_marker_str::<14>(input)?.to_string())
+            }),
+        ],
+    },
+    Implementations {
+        year: 2022,
+        day: 8,
+        part: Part::One,
+        solvers: &[
+            ("walking", |input| {
+                let forest = day08::Day8.parse(input)?;
+                Ok(day08::count_visible_walking(&forest).to_string())
+            }),
+            ("sweeping", |input| {
+                let forest = day08::Day8.parse(input)?;
+                Ok(day08::count_visible(&forest).to_string())
+            }),
+        ],
+    },
+];
+
+impl Implementations {
+    /// Names of the solvers separated by commas
+    pub fn names(&self) -> String {
+        let names = self.solvers.iter().map(|(name, _)| *name);
+        names.collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Registered implementations of the given parts of the days
+pub fn selected(days: &[&dyn solution::Runner], parts: &[Part]) -> Vec<&'static Implementations> {
+    IMPLEMENTATIONS
+        .iter()
+        .filter(|i| days.iter().any(|d| (d.year(), d.day()) == (i.year, i.day)))
+        .filter(|i| parts.contains(&i.part))
+        .collect()
+}
+
+/// Input the implementations of a part disagree on
+pub struct Disagreement {
+    pub input: String,
+    /// Answer of every implementation, none if it failed
+    pub answers: Vec<(&'static str, Option<String>)>,
+    /// Seed and case the input was generated with and how often it got smaller
+    pub seed: u64,
+    pub case: usize,
+    pub shrinks: usize,
+}
+
+/// Run all implementations of a part on `cases` inputs generated from `seed`
+/// with the generator of its day, returns the first input they disagree on
+/// shrunk as far as possible. Failing implementations agree with each other.
+pub fn compare<G>(
+    implementations: &Implementations,
+    generate: G,
+    seed: u64,
+    cases: usize,
+) -> Option<Disagreement>
+where
+    G: Fn(&mut Gen) -> String,
+{
+    let Failure {
+        seed,
+        case,
+        shrinks,
+        value: input,
+    } = prop::find_failure(seed, cases, generate, |input| {
+        let answers = answers(implementations, input);
+        answers.iter().all(|(_, answer)| *answer == answers[0].1)
+    })?;
+
+    Some(Disagreement {
+        answers: answers(implementations, &input),
+        input,
+        seed,
+        case,
+        shrinks,
+    })
+}
+
+/// Outcome of comparing the implementations of a part
+pub struct Comparison {
+    pub implementations: &'static Implementations,
+    /// The input they disagree on, none if they agree,
+    /// an error message if the day can't generate inputs
+    pub disagreement: std::result::Result<Option<Disagreement>, String>,
+}
+
+/// [`compare`] the implementations of the given parts of the days, lazily so the
+/// comparisons can be shown as they come. Fails if no part has more than one.
+pub fn compare_selected<'a>(
+    days: &'a [&'static dyn solution::Runner],
+    parts: &[Part],
+    seed: u64,
+    cases: usize,
+) -> std::result::Result<impl Iterator<Item = Comparison> + 'a, String> {
+    let selected = selected(days, parts);
+    if selected.is_empty() {
+        return Err(String::from(
+            "no selected part has more than one implementation",
+        ));
+    }
+
+    Ok(selected.into_iter().map(move |implementations| {
+        let (year, day) = (implementations.year, implementations.day);
+        let generator = days
+            .iter()
+            .find(|d| (d.year(), d.day()) == (year, day))
+            .and_then(|d| d.generator());
+        Comparison {
+            implementations,
+            disagreement: match generator {
+                Some(generate) => Ok(compare(implementations, generate, seed, cases)),
+                None => Err(format!("day {day} of {year} has no input generator yet")),
+            },
+        }
+    }))
+}
+
+fn answers(implementations: &Implementations, input: &str) -> Vec<(&'static str, Option<String>)> {
+    implementations
+        .solvers
+        .iter()
+        .map(|(name, solve)| (*name, solve(input).ok()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::REGISTRY;
+
+    #[test]
+    fn reports_the_smallest_disagreement() {
+        // counts elves with lines instead of paragraphs
+        let implementations = Implementations {
+            year: 2022,
+            day: 1,
+            part: Part::One,
+            solvers: &[
+                ("elves", |input| Ok(input.split("\n\n").count().to_string())),
+                ("lines", |input| Ok(input.lines().count().to_string())),
+            ],
+        };
+        let disagreement = compare(&implementations, day01::generate, 1, 50).unwrap();
+        assert!(disagreement.shrinks > 0);
+        assert_eq!(disagreement.input.lines().count(), 2);
+        assert_eq!(
+            disagreement.answers,
+            [
+                ("elves", Some(String::from("1"))),
+                ("lines", Some(String::from("2")))
+            ]
+        );
+    }
+
+    #[test]
+    fn selects_implementations_of_parts() {
+        let day6: &dyn solution::Runner = &day06::Day6;
+        assert_eq!(selected(&[day6], &[Part::One, Part::Two]).len(), 2);
+        assert_eq!(selected(&[day6, &day08::Day8], &[Part::Two]).len(), 1);
+        assert!(selected(&[&day08::Day8], &[Part::Two]).is_empty());
+
+        let days: &[&dyn solution::Runner] = &[&day06::Day6];
+        let comparisons = compare_selected(days, &[Part::Two], 1, 20).ok().unwrap();
+        let comparisons = comparisons.collect::<Vec<_>>();
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].implementations.names(), "window, last-seen");
+        assert!(matches!(comparisons[0].disagreement, Ok(None)));
+        assert_eq!(
+            compare_selected(&[&day08::Day8], &[Part::Two], 1, 20)
+                .err()
+                .unwrap(),
+            "no selected part has more than one implementation"
+        );
+    }
+
+    #[test]
+    fn registered_implementations_agree() {
+        for implementations in IMPLEMENTATIONS {
+            let day = REGISTRY
+                .iter()
+                .find(|d| (d.year(), d.day()) == (implementations.year, implementations.day))
+                .unwrap();
+            let generate = day.generator().unwrap();
+            assert!(implementations.solvers.len() > 1);
+            assert!(compare(implementations, generate, prop::DEFAULT_SEED, 100).is_none());
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufReader},
+};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// File could not be read or written
+    Io { path: String, source: io::Error },
+    /// Input is malformed, line and column are 1 based
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// Input is well formed but contains no answer to the puzzle
+    NoSolution(String),
+}
+
+impl Error {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Error {
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Error {
+        Error::NoSolution(message.into())
+    }
+
+    /// Message about an error of the given input file, I/O errors name it already
+    pub fn in_file(&self, filename: &str) -> String {
+        match self {
+            Error::Io { .. } => self.to_string(),
+            _ => format!("{filename}: {self}"),
+        }
+    }
+
+    /// Error message pointing into the input file, parse errors show
+    /// the offending line with a caret below the column
+    pub fn diagnostic(&self, filename: &str, input: &str) -> String {
+        let Error::Parse {
+            line,
+            column,
+            message,
+        } = self
+        else {
+            return format!("error: {self}\n");
+        };
+
+        let text = input.lines().nth(line - 1).unwrap_or("");
+        let number = line.to_string();
+        let gutter = " ".repeat(number.len());
+        // keep tabs so the caret lines up with the excerpt
+        let indent = text
+            .chars()
+            .chain(std::iter::repeat(' '))
+            .take(column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        format!(
+            "error: {message}\n\
+             {gutter}--> {filename}:{line}:{column}\n\
+             {gutter} |\n\
+             {number} | {text}\n\
+             {gutter} | {indent}^\n"
+        )
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "{path}: {source}"),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "parse error at line {line}, column {column}: {message}"),
+            Error::NoSolution(message) => write!(f, "no solution: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Read the puzzle input from the given file
+pub fn read_input(filename: &str) -> Result<String> {
+    std::fs::read_to_string(filename).map_err(|source| Error::Io {
+        path: filename.to_string(),
+        source,
+    })
+}
+
+/// Run `read` over a buffered reader of the given file, so the input can be
+/// processed without loading all of it. Read errors name the file.
+pub fn stream_input<T, F>(filename: &str, read: F) -> Result<T>
+where
+    F: FnOnce(BufReader<File>) -> Result<T>,
+{
+    let io_error = |source| Error::Io {
+        path: filename.to_string(),
+        source,
+    };
+    let file = File::open(filename).map_err(io_error)?;
+    read(BufReader::new(file)).map_err(|err| match err {
+        Error::Io { source, .. } => io_error(source),
+        err => err,
+    })
+}
+
+/// Error reading a stream without a name, [`stream_input`] replaces
+/// the placeholder path with the file name
+pub fn read_error(source: io::Error) -> Error {
+    Error::Io {
+        path: String::from("<input>"),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnostics_point_at_the_column() {
+        let input = "first\n\tab c\n";
+        assert_eq!(
+            Error::parse(2, 4, "invalid 'b'").diagnostic("in.txt", input),
+            "error: invalid 'b'\n --> in.txt:2:4\n  |\n2 | \tab c\n  | \t  ^\n"
+        );
+        // lines past the end of the input show nothing but the caret
+        assert_eq!(
+            Error::parse(10, 2, "missing").diagnostic("in.txt", input),
+            "error: missing\n  --> in.txt:10:2\n   |\n10 | \n   |  ^\n"
+        );
+        assert_eq!(
+            Error::no_solution("none").diagnostic("in.txt", input),
+            "error: no solution: none\n"
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::error::{Error, Result};
+
+/// Position in a grid, `x` is the column and `y` the row, both 0 based
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize) -> Point {
+        Point { x, y }
+    }
+
+    /// Neighbouring point in the given direction, none if it would be negative
+    pub fn step(self, direction: Direction) -> Option<Point> {
+        let (dx, dy) = direction.offset();
+        Some(Point {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// Directions sharing an edge
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+    /// Directions sharing an edge or a corner
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    /// (x, y) offset of a single step, up decreases y
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (1, -1),
+            Direction::DownLeft => (-1, 1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+}
+
+/// Rectangular grid stored row by row
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid with every cell set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Grid from rows of equal length, returns the index of the first row
+    /// with a different length otherwise
+    pub fn from_rows(rows: Vec<Vec<T>>) -> std::result::Result<Grid<T>, usize> {
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(i) = rows.iter().position(|row| row.len() != width) {
+            return Err(i);
+        }
+
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parse a map with one character per cell and one line per row.
+    /// `parse` turns a character into a cell or describes why it can't.
+    pub fn parse<F>(input: &str, parse: F) -> Result<Grid<T>>
+    where
+        F: Fn(char) -> std::result::Result<T, String>,
+    {
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(j, c)| parse(c).map_err(|message| Error::parse(i + 1, j + 1, message)))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let width = rows.first().map_or(0, Vec::len);
+        Grid::from_rows(rows).map_err(|i| {
+            let found = input.lines().nth(i).map_or(0, |line| line.chars().count());
+            Error::parse(
+                i + 1,
+                1,
+                format!("expected {width} characters per row, found {found}"),
+            )
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        match self.contains(point) {
+            true => self.cells.get(point.y * self.width + point.x),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        match self.contains(point) {
+            true => self.cells.get_mut(point.y * self.width + point.x),
+            false => None,
+        }
+    }
+
+    /// All points row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point { x, y }))
+    }
+
+    /// Cells of row `y`, panics if it's out of bounds
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Cells of column `x` from top to bottom, panics if it's out of bounds
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Cells starting next to `from` in the given direction up to the edge
+    pub fn walk(&self, from: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> {
+        let mut point = Some(from);
+        std::iter::from_fn(move || {
+            let next = point?.step(direction).filter(|p| self.contains(*p));
+            point = next;
+            Some((next?, &self[next?]))
+        })
+    }
+
+    /// Neighbours sharing an edge with `point`
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Direction::ORTHOGONAL)
+    }
+
+    /// Neighbours sharing an edge or a corner with `point`
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Direction::ALL)
+    }
+
+    fn neighbours(
+        &self,
+        point: Point,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = Point> + '_ {
+        directions
+            .iter()
+            .filter_map(move |direction| point.step(*direction))
+            .filter(|p| self.contains(*p))
+    }
+
+    /// Grid of the same size with `f` applied to every cell
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} out of bounds"))
+    }
+}
+
+/// One line per row with the cells printed next to each other
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", Ok).unwrap()
+    }
+
+    #[test]
+    fn parse_and_print() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        assert!(matches!(
+            Grid::parse("ab\nc\n", Ok),
+            Err(Error::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            Grid::parse("12\n3x\n", |c| c
+                .to_digit(10)
+                .ok_or(String::from("not a digit"))),
+            Err(Error::Parse {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn rows_columns_and_walks() {
+        let grid = grid();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        let walk = |x, y, direction| {
+            grid.walk(Point::new(x, y), direction)
+                .map(|(_, c)| *c)
+                .collect::<String>()
+        };
+        assert_eq!(walk(2, 0, Direction::Left), "ba");
+        assert_eq!(walk(0, 0, Direction::DownRight), "e");
+        assert_eq!(walk(0, 0, Direction::Up), "");
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = grid();
+        let corner = Point::new(0, 0);
+        assert_eq!(grid.neighbours4(corner).count(), 2);
+        assert_eq!(grid.neighbours8(corner).count(), 3);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 5);
+    }
+}
@@ -0,0 +1,313 @@
+use std::{
+    io::{self, Read, Write},
+    net::TcpStream,
+    process,
+    time::Duration,
+};
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Send a request and wait for the whole response.
+/// Plain `http://` URLs are handled in process, `https://` needs TLS
+/// which std doesn't provide, so those requests are delegated to `curl`.
+pub fn request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> io::Result<Response> {
+    match url.split_once("://") {
+        Some(("http", rest)) => plain_request(method, rest, headers, body),
+        Some(("https", _)) => curl_request(method, url, headers, body),
+        _ => Err(invalid_data(format!("unsupported URL '{url}'"))),
+    }
+}
+
+fn plain_request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> io::Result<Response> {
+    let (host, path) = match url.find('/') {
+        Some(index) => url.split_at(index),
+        None => (url, "/"),
+    };
+    let address = match host.contains(':') {
+        true => host.to_string(),
+        false => format!("{host}:80"),
+    };
+
+    let mut stream = TcpStream::connect(address)?;
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+
+    let mut request = format!("{method} {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n");
+    for (name, value) in headers {
+        request.push_str(&format!("{name}: {value}\r\n"));
+    }
+    if let Some(body) = body {
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(body.unwrap_or(""));
+    stream.write_all(request.as_bytes())?;
+
+    let mut response = vec![];
+    stream.read_to_end(&mut response)?;
+    parse_response(&response)
+}
+
+fn parse_response(response: &[u8]) -> io::Result<Response> {
+    let split =
+        find(response, b"\r\n\r\n").ok_or_else(|| invalid_data("incomplete HTTP response"))?;
+    let (head, body) = (
+        String::from_utf8_lossy(&response[..split]),
+        &response[split + 4..],
+    );
+
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split(' ').nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| invalid_data("invalid HTTP status line"))?;
+
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding") && value.trim() == "chunked"
+        })
+    });
+    // chunk sizes count bytes, so the body only becomes text once it's put together
+    let body = match chunked {
+        true => String::from_utf8_lossy(&decode_chunked(body)?).into_owned(),
+        false => String::from_utf8_lossy(body).into_owned(),
+    };
+
+    Ok(Response { status, body })
+}
+
+fn decode_chunked(mut body: &[u8]) -> io::Result<Vec<u8>> {
+    let mut decoded = vec![];
+    loop {
+        let end = find(body, b"\r\n").ok_or_else(|| invalid_data("invalid chunk"))?;
+        let size = String::from_utf8_lossy(&body[..end]);
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or("").trim(), 16)
+            .map_err(|_| invalid_data("invalid chunk size"))?;
+        if size == 0 {
+            return Ok(decoded);
+        }
+        let rest = &body[end + 2..];
+        let chunk = rest
+            .get(..size)
+            .ok_or_else(|| invalid_data("truncated chunk"))?;
+        decoded.extend_from_slice(chunk);
+        body = &rest[size..];
+        body = body.strip_prefix(b"\r\n").unwrap_or(body);
+    }
+}
+
+/// Index of the first occurrence of `needle` in `haystack`
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn curl_request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> io::Result<Response> {
+    let (mut command, config) = curl_command(method, url, headers, body);
+    let mut child = command
+        .stdin(process::Stdio::piped())
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::piped())
+        .spawn()?;
+    // dropping stdin closes it, so curl stops reading the config
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(config.as_bytes())?;
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "curl failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let output = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = output
+        .rsplit_once('\n')
+        .ok_or_else(|| invalid_data("missing status code"))?;
+    Ok(Response {
+        status: status
+            .parse()
+            .map_err(|_| invalid_data("invalid status code"))?,
+        body: body.to_string(),
+    })
+}
+
+/// The curl command and the config it reads from stdin. Headers and the body
+/// are only in the config, the arguments of a process are visible to every user.
+fn curl_command(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> (process::Command, String) {
+    let mut command = process::Command::new("curl");
+    command.args(["--silent", "--show-error", "--config", "-"]);
+
+    let mut config = format!("request = {}\n", quoted(method));
+    for (name, value) in headers {
+        config.push_str(&format!(
+            "header = {}\n",
+            quoted(&format!("{name}: {value}"))
+        ));
+    }
+    if let Some(body) = body {
+        config.push_str(&format!("data-raw = {}\n", quoted(body)));
+    }
+    // the status code is appended to the body on a line of its own
+    config.push_str(&format!("write-out = {}\n", quoted("\n%{http_code}")));
+    config.push_str(&format!("url = {}\n", quoted(url)));
+    (command, config)
+}
+
+/// Quoted value of a curl config file
+fn quoted(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Minimal HTTP server for tests, answering requests on localhost
+#[cfg(test)]
+pub mod stub {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serve one connection per given `(status, body)` response in order,
+    /// returns the base URL of the server and a handle yielding the raw requests
+    pub fn serve(responses: Vec<(u16, String)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                requests.push(read_request(&mut stream));
+                let response = format!(
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+
+        (url, handle)
+    }
+
+    fn read_request(stream: &mut impl Read) -> String {
+        let mut request = vec![];
+        let mut buffer = [0; 1024];
+        loop {
+            let n = stream.read(&mut buffer).unwrap();
+            request.extend_from_slice(&buffer[..n]);
+            let text = String::from_utf8_lossy(&request);
+            let complete = text.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+                let length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .map_or(0, |length| length.parse().unwrap());
+                body.len() >= length
+            });
+            if n == 0 || complete {
+                return text.to_string();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_from_stub_server() {
+        let (url, server) = stub::serve(vec![(200, String::from("1\n2\n"))]);
+
+        let response = request("GET", &format!("{url}/x"), &[("Cookie", "a=b")], None).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "1\n2\n");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /x HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: a=b\r\n"));
+    }
+
+    #[test]
+    fn curl_gets_secrets_from_stdin() {
+        let (command, config) = curl_command(
+            "POST",
+            "https://example.com/answer",
+            &[("Cookie", "session=secret")],
+            Some("level=1&answer=\"42\""),
+        );
+        assert!(command
+            .get_args()
+            .all(|arg| !arg.to_string_lossy().contains("secret")));
+        assert!(config.contains("header = \"Cookie: session=secret\"\n"));
+        assert!(config.contains("data-raw = \"level=1&answer=\\\"42\\\"\"\n"));
+        assert!(config.contains("write-out = \"\\n%{http_code}\"\n"));
+    }
+
+    #[test]
+    fn decodes_chunked_body() {
+        let response =
+            parse_response(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n2\r\nde\r\n0\r\n\r\n")
+                .unwrap();
+        assert_eq!(response.body, "abcde");
+
+        // sizes count bytes, "é" and "→" take 2 and 3 of them
+        let response = parse_response(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\naé\r\n4\r\n→b\r\n0\r\n\r\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(response.body, "aé→b");
+        // a chunk may even end inside a character
+        let response = parse_response(
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n2\r\na\xc3\r\n1\r\n\xa9\r\n0\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(response.body, "aé");
+    }
+}
@@ -0,0 +1,52 @@
+//! Advent of Code solutions
+//!
+//! Every year is a module with one public module per day, e.g. [`y2022::day05`],
+//! each exposing its solver functions and the types they work with.
+//! Solver functions take the path of the input file, e.g. [`y2022::day01::top_n_elves`],
+//! their `_str` variants take the puzzle input itself and their `_reader` variants,
+//! if a day has them, read it a buffer at a time. A day's `generate` function gives
+//! random valid puzzle inputs for property tests, see [`prop`].
+//! The remaining modules are the shared utilities the `adventofcode` binary
+//! is built from.
+
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod commands;
+pub mod diff;
+pub mod error;
+pub mod grid;
+mod http;
+pub mod output;
+pub mod parse;
+mod pool;
+pub mod prop;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod watch;
+
+pub mod y2022;
+
+use solution::Runner;
+
+/// All days that can be run, ordered by year and day.
+/// Adding a new day only requires a new entry here.
+pub static REGISTRY: &[&dyn Runner] = &[
+    &y2022::day01::Day1,
+    &y2022::day02::Day2,
+    &y2022::day03::Day3,
+    &y2022::day04::Day4,
+    &y2022::day05::Day5,
+    &y2022::day06::Day6,
+    &y2022::day07::Day7,
+    &y2022::day08::Day8,
+];
+
+/// Default location of the puzzle input of a day
+pub fn input_file(year: u32, day: u32) -> String {
+    format!("data/{year}/{day:02}.txt")
+}
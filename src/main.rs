@@ -1,82 +1,621 @@
-mod aoc1;
-mod aoc2;
-mod aoc3;
-mod aoc4;
-mod aoc5;
-mod aoc6;
-mod aoc7;
-mod aoc8;
+mod cli;
+
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    process::exit,
+    thread,
+};
+
+use adventofcode::{
+    alloc::{self, CountingAllocator},
+    answers::Answers,
+    bench::{self, Baseline},
+    client::{self, Client, Submission},
+    commands::{self, SubmitOutcome, Tally},
+    diff::{self, Comparison},
+    output::{self, Format, Record, Status},
+    repl, report, scaffold,
+    solution::{check_examples, Runner},
+    watch::Watcher,
+    REGISTRY,
+};
+use cli::{
+    BenchOptions, Command, DiffOptions, GenerateOptions, ReportOptions, RunOptions, SubmitOptions,
+    VerifyOptions, WatchOptions,
+};
+
+// only counts while enabled by --alloc
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 fn main() {
-    println!(
-        "AOC day 1, top elf: {}",
-        aoc1::top_n_elves("data/aoc1.txt", 1)
-    );
-    println!(
-        "AOC day 1, top 3 elves: {}",
-        aoc1::top_n_elves("data/aoc1.txt", 3)
-    );
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{}", cli::USAGE);
+            exit(2);
+        }
+    };
 
-    println!(
-        "AOC day 2, total points (part 1): {}",
-        aoc2::total_points("data/aoc2.txt", true)
-    );
-    println!(
-        "AOC day 2, total points (part 2): {}",
-        aoc2::total_points("data/aoc2.txt", false)
-    );
+    let success = match command {
+        Command::Run(options) => run(&options),
+        Command::Verify(options) => verify(&options),
+        Command::Bench(options) => bench(&options),
+        Command::Check(options) => check(&options),
+        Command::NewDay { year, day, title } => new_day(year, day, &title),
+        Command::Submit(options) => submit(&options),
+        Command::Watch(options) => watch(&options),
+        Command::Report(options) => report(&options),
+        Command::Repl(options) => repl(&options),
+        Command::Generate(options) => generate(&options),
+        Command::Diff(options) => diff(&options),
+        Command::Fetch { years, days } => fetch(&years, &days),
+        Command::List => {
+            list();
+            true
+        }
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            true
+        }
+    };
 
-    println!(
-        "AOC day 3 (part 1): {}",
-        aoc3::sum_priorities("data/aoc3.txt")
-    );
-    println!(
-        "AOC day 3 (part 2): {}",
-        aoc3::sum_group_priorities("data/aoc3.txt")
-    );
+    if !success {
+        exit(1);
+    }
+}
 
-    println!(
-        "AOC day 4 (part 1): {}",
-        aoc4::sum_full_overlaps("data/aoc4.txt")
-    );
-    println!(
-        "AOC day 4 (part 2): {}",
-        aoc4::sum_partial_overlaps("data/aoc4.txt")
-    );
+/// Registered days selected by the options, none if a selected day doesn't exist
+fn selected_days(options: &RunOptions) -> Option<Vec<&'static dyn Runner>> {
+    commands::select_days(&options.years, &options.days, options.input.is_some())
+        .map_err(|err| eprintln!("error: {err}"))
+        .ok()
+}
 
-    println!(
-        "AOC day 5 (part 1): {}",
-        aoc5::top_crates("data/aoc5.txt", false)
-    );
-    println!(
-        "AOC day 5 (part 1): {}",
-        aoc5::top_crates("data/aoc5.txt", true)
-    );
+/// The only day selected by the options, none unless there is exactly one
+fn selected_day(options: &RunOptions) -> Option<&'static dyn Runner> {
+    selected_days(options)?;
+    commands::select_day(&options.years, &options.days)
+        .map_err(|err| eprintln!("error: {err}"))
+        .ok()
+}
 
-    println!(
-        "AOC day 6 (part 1): {}",
-        aoc6::chars_before_marker::<4>("data/aoc6.txt")
-    );
-    println!(
-        "AOC day 6 (part 2): {}",
-        aoc6::chars_before_marker::<14>("data/aoc6.txt")
-    );
+/// Run all selected days, returns false if any of them failed
+fn run(options: &RunOptions) -> bool {
+    let Some(days) = selected_days(options) else {
+        return false;
+    };
+    if options.alloc {
+        alloc::enable();
+    }
 
-    println!(
-        "AOC day 7 (part 1): {}",
-        aoc7::size_of_dirs_below("data/aoc7.txt", 100000)
+    if options.examples {
+        let mut success = true;
+        for day in days {
+            println!("AOC {} day {}: {}", day.year(), day.day(), day.title());
+            success &= run_examples(day, options);
+        }
+        return success;
+    }
+
+    let records = commands::run(
+        &days,
+        &options.parts(),
+        |day| options.input_file(day.year(), day.day()),
+        options.jobs,
     );
-    println!(
-        "AOC day 7 (part 2): {}",
-        aoc7::dir_to_delete("data/aoc7.txt", 70000000, 30000000)
+    match options.format {
+        Format::Text => print_run(&records),
+        format => print!("{}", output::render(format, &records)),
+    }
+
+    records.iter().all(|r| r.status == Status::Ok)
+}
+
+/// Print the header of a day if `record` is the first one of it
+fn print_day_header(records: &[Record], i: usize) {
+    let r = &records[i];
+    if i == 0 || (records[i - 1].year, records[i - 1].day) != (r.year, r.day) {
+        println!("AOC {} day {}: {}", r.year, r.day, r.title);
+    }
+}
+
+fn print_run(records: &[Record]) {
+    for (i, r) in records.iter().enumerate() {
+        print_day_header(records, i);
+        match &r.answer {
+            Some(answer) => {
+                let alloc = r.alloc.map_or(String::from(""), |a| {
+                    format!(
+                        ", {} allocations, {} allocated, peak {}",
+                        a.allocations,
+                        alloc::format_bytes(a.bytes),
+                        alloc::format_bytes(a.peak)
+                    )
+                });
+                println!(
+                    "  part {}: {answer} (parse {}, solve {}{alloc})",
+                    r.part,
+                    bench::format_duration(r.parse_time),
+                    bench::format_duration(r.solve_time)
+                )
+            }
+            None => eprintln!(
+                "  part {}: error: {}",
+                r.part,
+                r.detail.as_deref().unwrap_or("")
+            ),
+        }
+    }
+}
+
+fn run_examples(day: &dyn Runner, options: &RunOptions) -> bool {
+    let mut success = true;
+    for part in options.parts() {
+        let failures = check_examples(day, part);
+        if failures.is_empty() {
+            println!("  part {part}: ok");
+        } else {
+            for failure in failures {
+                eprintln!("  {failure}");
+            }
+            success = false;
+        }
+    }
+
+    success
+}
+
+/// Compare the answers of all selected days with the known answers,
+/// returns false if any of them differs or failed
+fn verify(options: &VerifyOptions) -> bool {
+    let Some(days) = selected_days(&options.run) else {
+        return false;
+    };
+    if options.run.alloc {
+        alloc::enable();
+    }
+    let mut answers = match Answers::load(&options.answers) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("error: {}: {err}", options.answers);
+            return false;
+        }
+    };
+
+    let records = commands::verify(
+        &days,
+        &options.run.parts(),
+        |day| options.run.input_file(day.year(), day.day()),
+        options.run.jobs,
+        &mut answers,
+        options.accept,
     );
+    let Tally {
+        passed,
+        failed,
+        new,
+    } = Tally::of(&records);
 
-    println!(
-        "AOC day 8 (part 1): {}",
-        aoc8::visible_trees("data/aoc8.txt")
+    match options.run.format {
+        Format::Text => print_verify(&records),
+        format => print!("{}", output::render(format, &records)),
+    }
+
+    if options.accept && new > 0 {
+        match answers.save() {
+            // keep structured output on stdout parseable
+            Ok(()) if options.run.format != Format::Text => {
+                eprintln!("recorded {new} new answer(s) in {}", options.answers)
+            }
+            Ok(()) => println!("recorded {new} new answer(s) in {}", options.answers),
+            Err(err) => {
+                eprintln!("error: {err}");
+                return false;
+            }
+        }
+    }
+
+    if options.run.format == Format::Text {
+        println!("{passed} passed, {failed} failed, {new} new");
+    }
+    failed == 0
+}
+
+fn print_verify(records: &[Record]) {
+    for (i, r) in records.iter().enumerate() {
+        print_day_header(records, i);
+        let answer = r.answer.as_deref().unwrap_or("");
+        match r.status {
+            Status::Pass => println!("  part {}: PASS", r.part),
+            Status::New => println!("  part {}: NEW {answer}", r.part),
+            Status::Fail => {
+                println!("  part {}: FAIL", r.part);
+                match &r.expected {
+                    Some(expected) => println!("    - {expected}"),
+                    None => println!("    {}", r.detail.as_deref().unwrap_or("")),
+                }
+                println!("    + {answer}");
+            }
+            _ => {
+                println!("  part {}: FAIL", r.part);
+                println!("    error: {}", r.detail.as_deref().unwrap_or(""));
+            }
+        }
+    }
+}
+
+/// Benchmark all selected days, returns false if any of them failed
+fn bench(options: &BenchOptions) -> bool {
+    let Some(days) = selected_days(&options.run) else {
+        return false;
+    };
+    let baseline = match options.baseline.as_deref().map(Baseline::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(err) => {
+            eprintln!(
+                "error: {}: {err}",
+                options.baseline.as_deref().unwrap_or("")
+            );
+            return false;
+        }
+    };
+
+    let mut success = true;
+    let mut measurements = vec![];
+    let parts = options.run.parts();
+    let input_file = |day: &dyn Runner| options.run.input_file(day.year(), day.day());
+    bench::print_header(baseline.is_some());
+    for (day, result) in bench::bench_days(&days, &parts, input_file, options.runs, options.warmup)
+    {
+        match result {
+            Ok(day_measurements) => {
+                for m in &day_measurements {
+                    bench::print_measurement(m, baseline.as_ref());
+                }
+                measurements.extend(day_measurements);
+            }
+            Err(err) => {
+                eprintln!("error: {} day {}: {err}", day.year(), day.day());
+                success = false;
+            }
+        }
+    }
+
+    if let Some(path) = &options.save {
+        match bench::save_baseline(path, &measurements) {
+            Ok(()) => println!("saved baseline to {path}"),
+            Err(err) => {
+                eprintln!("error: {err}");
+                success = false;
+            }
+        }
+    }
+
+    success
+}
+
+fn new_day(year: Option<u32>, day: u32, title: &str) -> bool {
+    let year = match commands::years_or_latest(year.as_slice()) {
+        Ok(years) => years[0],
+        Err(err) => {
+            eprintln!("error: {err}");
+            return false;
+        }
+    };
+
+    match scaffold::new_day(Path::new("."), year, day, title) {
+        Ok(files) => {
+            for file in files {
+                println!("wrote {file}");
+            }
+            true
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            false
+        }
+    }
+}
+
+/// Submit the answer of the selected part and record the verdict,
+/// returns false unless the answer was correct
+fn submit(options: &SubmitOptions) -> bool {
+    let Some(day) = selected_day(&options.run) else {
+        return false;
+    };
+    let part = options.run.parts()[0];
+
+    let filename = options.run.input_file(day.year(), day.day());
+    let submitted = commands::submit_part(
+        day,
+        part,
+        &filename,
+        options.answer.as_deref(),
+        &options.answers,
+        |answer| Client::from_env()?.submit(day.year(), day.day(), part, answer),
     );
+    let (answer, outcome) = match submitted {
+        Ok(submitted) => submitted,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return false;
+        }
+    };
+    match &outcome {
+        SubmitOutcome::Rejected(reason) => eprintln!("not submitting {answer}: {reason}"),
+        SubmitOutcome::Submitted(Submission::Answered(verdict)) => println!(
+            "AOC {} day {} part {part}: {answer} is {verdict}",
+            day.year(),
+            day.day()
+        ),
+        SubmitOutcome::Submitted(Submission::RateLimited { wait }) => match wait {
+            Some(wait) => eprintln!("answered too recently, wait {}s", wait.as_secs()),
+            None => eprintln!("answered too recently, wait a bit"),
+        },
+        SubmitOutcome::Submitted(Submission::WrongLevel) => {
+            eprintln!("part {part} is already solved or not unlocked yet")
+        }
+    }
+
+    outcome.is_correct()
+}
+
+/// Run the selected day whenever its input or example file changes,
+/// only returns if the day can't be selected
+fn watch(options: &WatchOptions) -> bool {
+    let Some(day) = selected_day(&options.run) else {
+        return false;
+    };
+    if options.run.alloc {
+        alloc::enable();
+    }
+
+    let mut files = vec![options.run.input_file(day.year(), day.day())];
+    files.extend(options.example.clone());
+    let mut watcher = Watcher::new(files);
+
+    loop {
+        if watcher.changed() {
+            for file in watcher.files() {
+                println!("== {file}");
+                print_run(&commands::run(
+                    &[day],
+                    &options.run.parts(),
+                    |_| file.clone(),
+                    1,
+                ));
+            }
+            println!("watching for changes, press Ctrl-C to stop");
+        }
+        thread::sleep(options.interval);
+    }
+}
+
+/// Download the inputs of all selected days that don't have one yet,
+/// returns false if any download failed
+fn fetch(years: &[u32], days: &[u32]) -> bool {
+    let years = match commands::years_or_latest(years) {
+        Ok(years) => years,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return false;
+        }
+    };
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return false;
+        }
+    };
+
+    let mut success = true;
+    for year in years {
+        for day in days {
+            match client::fetch_input(&client, Path::new("."), year, *day) {
+                Ok((path, true)) => println!("downloaded {path}"),
+                Ok((path, false)) => println!("using cached {path}"),
+                Err(err) => {
+                    eprintln!("error: {year} day {day}: {err}");
+                    success = false;
+                }
+            }
+        }
+    }
+
+    success
+}
+
+fn list() {
+    for day in REGISTRY {
+        println!("{}  {:>2}  {}", day.year(), day.day(), day.title());
+    }
+}
+
+/// Validate the inputs of the selected days, returns false if any has a problem
+fn check(options: &RunOptions) -> bool {
+    let Some(days) = selected_days(options) else {
+        return false;
+    };
+
+    let mut problems = 0;
+    for day in days {
+        println!("AOC {} day {}: {}", day.year(), day.day(), day.title());
+        let filename = options.input_file(day.year(), day.day());
+        let diagnostics = commands::check_input(day, &filename);
+
+        match diagnostics.is_empty() {
+            true => println!("  ok"),
+            false => eprint!("{}", diagnostics.join("\n")),
+        }
+        problems += diagnostics.len();
+    }
+
+    println!("{problems} problem(s) found");
+    problems == 0
+}
+
+/// Print or write the Markdown report of the selected days
+fn report(options: &ReportOptions) -> bool {
+    let Some(days) = selected_days(&options.run) else {
+        return false;
+    };
+    let mut answers = match Answers::load(&options.answers) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("error: {}: {err}", options.answers);
+            return false;
+        }
+    };
+
+    let records = match options.no_run {
+        true => vec![],
+        false => commands::verify(
+            &days,
+            &options.run.parts(),
+            |day| options.run.input_file(day.year(), day.day()),
+            options.run.jobs,
+            &mut answers,
+            false,
+        ),
+    };
+    let table = report::markdown(&report::rows(&days, &records, &answers));
+
+    let Some(path) = &options.output else {
+        print!("{table}");
+        return true;
+    };
+    match fs::write(path, table) {
+        Ok(()) => {
+            println!("wrote the report to {path}");
+            true
+        }
+        Err(err) => {
+            eprintln!("error: {path}: {err}");
+            false
+        }
+    }
+}
+
+/// Explore the input of a single day with its REPL commands
+fn repl(options: &RunOptions) -> bool {
+    let Some(day) = selected_day(options) else {
+        return false;
+    };
+
+    let filename = options.input_file(day.year(), day.day());
+    let mut session = match commands::open_session(day, &filename) {
+        Ok(session) => session,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return false;
+        }
+    };
+
     println!(
-        "AOC day 8 (part 2): {}",
-        aoc8::highest_scenic_score("data/aoc8.txt")
+        "AOC {} day {}: {}, enter help for the commands",
+        day.year(),
+        day.day(),
+        day.title()
     );
+    let prompt = format!("day{}> ", day.day());
+    let mut stdout = io::stdout();
+    match repl::run(&mut *session, &prompt, io::stdin().lock(), &mut stdout) {
+        Ok(()) => stdout.flush().is_ok(),
+        Err(err) => {
+            eprintln!("error: {err}");
+            false
+        }
+    }
+}
+
+/// Write a random input of a single day, returns false if the day has no generator
+fn generate(options: &GenerateOptions) -> bool {
+    let Some(day) = selected_day(&options.run) else {
+        return false;
+    };
+    let input = match commands::generate_input(day, options.seed, options.size) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return false;
+        }
+    };
+    let Some(path) = &options.output else {
+        // a closed pipe like `| head` is not an error
+        let _ = io::stdout().lock().write_all(input.as_bytes());
+        return true;
+    };
+    match fs::write(path, &input) {
+        Ok(()) => {
+            println!(
+                "wrote {} of input for day {} to {path}",
+                alloc::format_bytes(input.len() as u64),
+                day.day()
+            );
+            true
+        }
+        Err(err) => {
+            eprintln!("error: {path}: {err}");
+            false
+        }
+    }
+}
+
+/// Compare the implementations of the selected parts on generated inputs,
+/// returns false if they disagree on any
+fn diff(options: &DiffOptions) -> bool {
+    let Some(days) = selected_days(&options.run) else {
+        return false;
+    };
+    let comparisons =
+        diff::compare_selected(&days, &options.run.parts(), options.seed, options.cases);
+    let comparisons = match comparisons {
+        Ok(comparisons) => comparisons,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return false;
+        }
+    };
+
+    let mut success = true;
+    for Comparison {
+        implementations,
+        disagreement,
+    } in comparisons
+    {
+        let (year, day, part) = (
+            implementations.year,
+            implementations.day,
+            implementations.part,
+        );
+        let names = implementations.names();
+        match disagreement {
+            Ok(None) => println!(
+                "AOC {year} day {day} part {part}: {names} agree on {} inputs",
+                options.cases
+            ),
+            Ok(Some(disagreement)) => {
+                success = false;
+                println!(
+                    "AOC {year} day {day} part {part}: {names} disagree \
+                     (seed {}, case {}, {} shrinks)",
+                    disagreement.seed, disagreement.case, disagreement.shrinks
+                );
+                for (name, answer) in &disagreement.answers {
+                    println!("  {name}: {}", answer.as_deref().unwrap_or("failed"));
+                }
+                print!("  input:\n{}", disagreement.input);
+            }
+            Err(err) => {
+                eprintln!("error: {err}");
+                success = false;
+            }
+        }
+    }
+
+    success
 }
@@ -0,0 +1,267 @@
+use std::{
+    fmt::{self, Write},
+    time::Duration,
+};
+
+use crate::{alloc::AllocStats, runner::DayResult, solution::Part};
+
+/// How the results of executed solutions are printed
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Human readable
+    Text,
+    Json,
+    Csv,
+}
+
+/// Status of a [`Record`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Status {
+    /// Solved when running
+    Ok,
+    /// Not solved, the detail tells why
+    Error,
+    /// Solved with the known answer when verifying
+    Pass,
+    /// Solved with another answer than the known one, or one known to be wrong
+    Fail,
+    /// Solved when verifying, but no answer is known yet
+    New,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Error => write!(f, "error"),
+            Status::Pass => write!(f, "pass"),
+            Status::Fail => write!(f, "fail"),
+            Status::New => write!(f, "new"),
+        }
+    }
+}
+
+/// Outcome of a single executed part
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    pub part: Part,
+    pub answer: Option<String>,
+    /// Known answer, only set when verifying
+    pub expected: Option<String>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// Only set if allocations were counted
+    pub alloc: Option<AllocStats>,
+    /// Ok or error when running, pass, fail, new or error when verifying
+    pub status: Status,
+    /// Error message or other details about the status
+    pub detail: Option<String>,
+}
+
+impl Record {
+    pub fn duration(&self) -> Duration {
+        self.parse_time + self.solve_time
+    }
+}
+
+/// One record per selected part of the day, with status [`Status::Ok`] or [`Status::Error`]
+pub fn records(result: &DayResult, parts: &[Part]) -> Vec<Record> {
+    let day = result.day;
+    let record = |part, answer, parse_time, solve_time, alloc, status, detail| Record {
+        year: day.year(),
+        day: day.day(),
+        title: day.title(),
+        part,
+        answer,
+        expected: None,
+        parse_time,
+        solve_time,
+        alloc,
+        status,
+        detail,
+    };
+
+    if let Some(err) = &result.error {
+        let detail = err.in_file(&result.filename);
+        return parts
+            .iter()
+            .map(|part| {
+                let zero = Duration::ZERO;
+                record(
+                    *part,
+                    None,
+                    zero,
+                    zero,
+                    None,
+                    Status::Error,
+                    Some(detail.clone()),
+                )
+            })
+            .collect();
+    }
+
+    result
+        .parts
+        .iter()
+        .map(|part| match &part.answer {
+            Ok(answer) => record(
+                part.part,
+                Some(answer.clone()),
+                part.parse_time,
+                part.solve_time,
+                part.alloc,
+                Status::Ok,
+                None,
+            ),
+            Err(err) => record(
+                part.part,
+                None,
+                part.parse_time,
+                part.solve_time,
+                part.alloc,
+                Status::Error,
+                Some(err.to_string()),
+            ),
+        })
+        .collect()
+}
+
+/// Records in the given structured format, nothing for [`Format::Text`]
+pub fn render(format: Format, records: &[Record]) -> String {
+    match format {
+        Format::Text => String::from(""),
+        Format::Json => to_json(records),
+        Format::Csv => to_csv(records),
+    }
+}
+
+/// JSON array with one object per record
+fn to_json(records: &[Record]) -> String {
+    let mut json = String::from("[");
+    for (i, r) in records.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        let _ = write!(
+            json,
+            "\n  {{\"year\": {}, \"day\": {}, \"part\": {}, \"answer\": {}, \"expected\": {}, \"duration_ns\": {}, \"allocations\": {}, \"allocated_bytes\": {}, \"peak_bytes\": {}, \"status\": {}, \"detail\": {}}}",
+            r.year,
+            r.day,
+            r.part,
+            json_string(r.answer.as_deref()),
+            json_string(r.expected.as_deref()),
+            r.duration().as_nanos(),
+            json_number(r.alloc.map(|a| a.allocations)),
+            json_number(r.alloc.map(|a| a.bytes)),
+            json_number(r.alloc.map(|a| a.peak)),
+            json_string(Some(&r.status.to_string())),
+            json_string(r.detail.as_deref())
+        );
+    }
+    json.push_str("\n]\n");
+    json
+}
+
+/// CSV table with a header line and one line per record
+fn to_csv(records: &[Record]) -> String {
+    let mut csv = String::from(
+        "year,day,part,answer,expected,duration_ns,allocations,allocated_bytes,peak_bytes,status,detail\n",
+    );
+    for r in records {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            r.year,
+            r.day,
+            r.part,
+            csv_field(r.answer.as_deref().unwrap_or("")),
+            csv_field(r.expected.as_deref().unwrap_or("")),
+            r.duration().as_nanos(),
+            csv_number(r.alloc.map(|a| a.allocations)),
+            csv_number(r.alloc.map(|a| a.bytes)),
+            csv_number(r.alloc.map(|a| a.peak)),
+            csv_field(&r.status.to_string()),
+            csv_field(r.detail.as_deref().unwrap_or(""))
+        );
+    }
+    csv
+}
+
+fn json_number(number: Option<u64>) -> String {
+    number.map_or(String::from("null"), |number| number.to_string())
+}
+
+fn csv_number(number: Option<u64>) -> String {
+    number.map_or(String::from(""), |number| number.to_string())
+}
+
+fn json_string(str: Option<&str>) -> String {
+    let Some(str) = str else {
+        return String::from("null");
+    };
+
+    let mut json = String::from("\"");
+    for c in str.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn csv_field(str: &str) -> String {
+    match str.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", str.replace('"', "\"\"")),
+        false => str.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(answer: &str, detail: Option<&str>) -> Record {
+        Record {
+            year: 2022,
+            day: 5,
+            title: "Supply Stacks",
+            part: Part::Two,
+            answer: Some(answer.to_string()),
+            expected: None,
+            parse_time: Duration::from_nanos(40),
+            solve_time: Duration::from_nanos(2),
+            alloc: None,
+            status: Status::Ok,
+            detail: detail.map(String::from),
+        }
+    }
+
+    #[test]
+    fn json_escapes_strings() {
+        let json = to_json(&[record("a\"b", Some("line\nbreak"))]);
+        assert_eq!(
+            json,
+            "[\n  {\"year\": 2022, \"day\": 5, \"part\": 2, \"answer\": \"a\\\"b\", \"expected\": null, \"duration_ns\": 42, \"allocations\": null, \"allocated_bytes\": null, \"peak_bytes\": null, \"status\": \"ok\", \"detail\": \"line\\nbreak\"}\n]\n"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_fields() {
+        let csv = to_csv(&[record("x,y", None)]);
+        assert_eq!(
+            csv,
+            "year,day,part,answer,expected,duration_ns,allocations,allocated_bytes,peak_bytes,status,detail\n2022,5,2,\"x,y\",,42,,,,ok,\n"
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use std::{io::BufRead, str::FromStr};
+
+use crate::error::{read_error, Error, Result};
+
+/// Piece of the input with the (1 based) line and column it starts at,
+/// so errors can point at the offending text
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<'a> Span<'a> {
+    /// Span of `part`, which has to be a slice of this span's text
+    pub fn sub(&self, part: &'a str) -> Span<'a> {
+        let offset = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        Span {
+            text: part,
+            line: self.line,
+            column: self.column + self.text[..offset].chars().count(),
+        }
+    }
+
+    /// Parse error located at the start of the span
+    pub fn error(&self, message: impl Into<String>) -> Error {
+        Error::parse(self.line, self.column, message)
+    }
+
+    /// Parse the whole text, `what` describes the value in the error message
+    pub fn parse<T: FromStr>(&self, what: &str) -> Result<T> {
+        self.text
+            .parse()
+            .map_err(|_| self.error(format!("invalid {what} '{}'", self.text)))
+    }
+
+    /// Whitespace separated words
+    pub fn words(&self) -> Vec<Span<'a>> {
+        self.text
+            .split_whitespace()
+            .map(|word| self.sub(word))
+            .collect()
+    }
+
+    /// Every character with a span of its own
+    pub fn chars(&self) -> impl Iterator<Item = (Span<'a>, char)> + '_ {
+        self.text
+            .char_indices()
+            .map(|(i, c)| (self.sub(&self.text[i..i + c.len_utf8()]), c))
+    }
+
+    /// Consecutive columns of `width` characters, the last one may be shorter
+    pub fn fixed_width(&self, width: usize) -> Vec<Span<'a>> {
+        let starts = self
+            .text
+            .char_indices()
+            .step_by(width)
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        starts
+            .iter()
+            .enumerate()
+            .map(|(i, start)| {
+                let end = starts.get(i + 1).copied().unwrap_or(self.text.len());
+                self.sub(&self.text[*start..end])
+            })
+            .collect()
+    }
+
+    /// All integers in the text, a '-' directly in front of digits is a sign
+    /// unless it follows another digit like in "2-4"
+    pub fn integers<T: FromStr>(&self) -> Result<Vec<T>> {
+        let bytes = self.text.as_bytes();
+        let mut integers = vec![];
+        let mut i = 0;
+        while i < bytes.len() {
+            let negative = bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !bytes[i - 1].is_ascii_digit());
+            if !negative && !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            integers.push(self.sub(&self.text[start..i]).parse("integer")?);
+        }
+        Ok(integers)
+    }
+
+    /// Split at the first separator, both sides are trimmed
+    pub fn split_once(&self, separator: &str) -> Option<(Span<'a>, Span<'a>)> {
+        let (first, second) = self.text.split_once(separator)?;
+        Some((self.sub(first.trim()), self.sub(second.trim())))
+    }
+
+    /// Split a "key value" line at the first separator
+    pub fn key_value(&self, separator: &str) -> Result<(Span<'a>, Span<'a>)> {
+        self.split_once(separator)
+            .filter(|(key, value)| !key.text.is_empty() && !value.text.is_empty())
+            .ok_or_else(|| self.error(format!("expected '<key>{separator}<value>'")))
+    }
+}
+
+/// All lines of the input
+pub fn lines(input: &str) -> impl Iterator<Item = Span<'_>> {
+    input.lines().enumerate().map(|(i, text)| Span {
+        text,
+        line: i + 1,
+        column: 1,
+    })
+}
+
+/// Lines of a reader read one at a time into the same buffer,
+/// so memory doesn't grow with the length of the input
+pub struct LineReader<R> {
+    reader: R,
+    buffer: String,
+    line: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> LineReader<R> {
+        LineReader {
+            reader,
+            buffer: String::new(),
+            line: 0,
+        }
+    }
+
+    /// Next line without its line ending like [`lines`], none at the end of the input
+    pub fn next_line(&mut self) -> Result<Option<Span<'_>>> {
+        self.buffer.clear();
+        if self
+            .reader
+            .read_line(&mut self.buffer)
+            .map_err(read_error)?
+            == 0
+        {
+            return Ok(None);
+        }
+        self.line += 1;
+
+        let text = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        Ok(Some(Span {
+            text,
+            line: self.line,
+            column: 1,
+        }))
+    }
+}
+
+/// Blocks of lines separated by one or more empty lines
+pub fn paragraphs(input: &str) -> Vec<Vec<Span<'_>>> {
+    let mut paragraphs = vec![];
+    let mut paragraph = vec![];
+    for line in lines(input) {
+        if !line.text.is_empty() {
+            paragraph.push(line);
+        } else if !paragraph.is_empty() {
+            paragraphs.push(paragraph);
+            paragraph = vec![];
+        }
+    }
+    if !paragraph.is_empty() {
+        paragraphs.push(paragraph);
+    }
+    paragraphs
+}
+
+/// Line pattern like `"move <n> from <stack> to <stack>"`,
+/// every `<name>` placeholder matches a non empty field
+pub struct Pattern {
+    pattern: &'static str,
+}
+
+impl Pattern {
+    pub const fn new(pattern: &'static str) -> Pattern {
+        Pattern { pattern }
+    }
+
+    /// The `N` fields of a matching span, none if it doesn't match
+    pub fn captures<'a, const N: usize>(&self, span: Span<'a>) -> Option<[Span<'a>; N]> {
+        let mut pieces = self.pattern.split('<');
+        let mut rest = span.text.strip_prefix(pieces.next()?)?;
+
+        let mut fields = vec![];
+        for piece in pieces {
+            let (_, literal) = piece.split_once('>')?;
+            // a field ends where the literal text following it starts
+            let end = match literal.is_empty() {
+                true => rest.len(),
+                false => rest.find(literal)?,
+            };
+            if end == 0 {
+                return None;
+            }
+            fields.push(span.sub(&rest[..end]));
+            rest = &rest[end + literal.len()..];
+        }
+
+        match rest.is_empty() {
+            true => fields.try_into().ok(),
+            false => None,
+        }
+    }
+
+    /// Same as [`Pattern::captures`] but a mismatch is an error showing the pattern
+    pub fn fields<'a, const N: usize>(&self, span: Span<'a>) -> Result<[Span<'a>; N]> {
+        self.captures(span)
+            .ok_or_else(|| span.error(format!("expected '{}'", self.pattern)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str) -> Span<'_> {
+        Span {
+            text,
+            line: 3,
+            column: 1,
+        }
+    }
+
+    #[test]
+    fn paragraphs_keep_line_numbers() {
+        let paragraphs = paragraphs("a\nb\n\n\nc\n");
+        assert_eq!(paragraphs.len(), 2);
+        assert_eq!(paragraphs[1][0].text, "c");
+        assert_eq!(paragraphs[1][0].line, 5);
+    }
+
+    #[test]
+    fn line_reader_matches_lines() {
+        let input = "a\r\n\nbc";
+        let mut reader = LineReader::new(input.as_bytes());
+        let mut read = vec![];
+        while let Some(line) = reader.next_line().unwrap() {
+            read.push((line.text.to_string(), line.line));
+        }
+        let expected = lines(input)
+            .map(|line| (line.text.to_string(), line.line))
+            .collect::<Vec<_>>();
+        assert_eq!(read, expected);
+    }
+
+    #[test]
+    fn columns_and_integers() {
+        let columns = span("[A] [B]").fixed_width(4);
+        assert_eq!(columns[1].text, "[B]");
+        assert_eq!(columns[1].column, 5);
+
+        assert_eq!(span("x=-3, 2-4").integers::<i32>().unwrap(), [-3, 2, 4]);
+        let err = span("1 99999999999").integers::<u32>().unwrap_err();
+        assert!(matches!(err, Error::Parse { column: 3, .. }));
+    }
+
+    #[test]
+    fn key_value_lines() {
+        let (key, value) = span("A  Y").key_value(" ").unwrap();
+        assert_eq!((key.text, value.text, value.column), ("A", "Y", 4));
+        assert!(span("A").key_value(" ").is_err());
+    }
+
+    #[test]
+    fn patterns_locate_fields() {
+        const MOVE: Pattern = Pattern::new("move <n> from <stack> to <stack>");
+
+        let [n, from, to] = MOVE.fields(span("move 12 from 1 to 3")).unwrap();
+        assert_eq!((n.text, from.text, to.text), ("12", "1", "3"));
+        assert_eq!(from.column, 14);
+        assert_eq!(to.parse::<u32>("stack").unwrap(), 3);
+
+        assert!(MOVE.captures::<3>(span("move 1 from 2 to")).is_none());
+        assert!(MOVE.captures::<3>(span("move 1 to 3")).is_none());
+        let err = MOVE.fields::<3>(span("mov 1")).unwrap_err();
+        assert!(err.to_string().contains("line 3, column 1"));
+    }
+}
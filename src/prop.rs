@@ -0,0 +1,332 @@
+use std::{env, fmt::Debug};
+
+/// Number of random cases [`check`] tries
+pub const CASES: usize = 256;
+/// Size of the last and largest case [`check`] tries
+pub const MAX_SIZE: usize = 50;
+/// Seed of the first case, can be changed with `AOC_PROP_SEED`
+pub const DEFAULT_SEED: u64 = 2022;
+/// Give up shrinking after this many tried candidates
+const MAX_SHRINKS: usize = 10_000;
+
+/// Small fast PRNG (SplitMix64), the same seed always gives the same numbers
+#[derive(Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Input generator of a day, see [`Solution::generator`](crate::solution::Solution::generator)
+pub type Generate = fn(&mut Gen) -> String;
+
+/// Source of the random choices of a generator.
+///
+/// While checking properties every choice is recorded, so a failing case can be
+/// generated again from smaller choices while shrinking. Smaller choices give
+/// smaller values, lists stop at a zero choice.
+pub struct Gen {
+    rng: Rng,
+    size: usize,
+    /// Choices to use instead of random ones, missing choices are zero
+    replay: Option<Vec<u64>>,
+    /// Keep the choices, generating large inputs doesn't need them
+    record: bool,
+    choices: Vec<u64>,
+}
+
+impl Gen {
+    /// Generator of random values, `size` is the average length of lists
+    pub fn new(seed: u64, size: usize) -> Gen {
+        Gen {
+            rng: Rng::new(seed),
+            size,
+            replay: None,
+            record: false,
+            choices: vec![],
+        }
+    }
+
+    fn recording(seed: u64, size: usize) -> Gen {
+        Gen {
+            record: true,
+            ..Gen::new(seed, size)
+        }
+    }
+
+    fn replaying(choices: Vec<u64>, size: usize) -> Gen {
+        Gen {
+            replay: Some(choices),
+            ..Gen::recording(0, size)
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number below `bound`, which has to be positive
+    pub fn choice(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "choice from an empty range");
+        let choice = match &self.replay {
+            Some(replay) => replay
+                .get(self.choices.len())
+                .map_or(0, |c| *c.min(&(bound - 1))),
+            None => self.rng.next_u64() % bound,
+        };
+        if self.record {
+            self.choices.push(choice);
+        }
+        choice
+    }
+
+    /// Number in the range, shrinks towards its start
+    pub fn range(&mut self, range: std::ops::RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {start}..={end}");
+        start + self.choice((end - start) as u64 + 1) as usize
+    }
+
+    /// True with the given chance in percent, shrinks towards false
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.choice(100) >= 100 - percent
+    }
+
+    /// One of the items, shrinks towards the first one
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    /// Random order of the items
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range(0..=i);
+            items.swap(i, j);
+        }
+    }
+
+    /// List of [`Gen::size`] elements on average, at least `min`
+    pub fn list<T, F>(&mut self, min: usize, mut element: F) -> Vec<T>
+    where
+        F: FnMut(&mut Gen) -> T,
+    {
+        let mut list = vec![];
+        // every element is preceded by the choice to continue,
+        // so removing choices removes whole elements
+        while list.len() < min || self.choice(self.size as u64 + 1) > 0 {
+            list.push(element(self));
+        }
+        list
+    }
+}
+
+/// Check that `property` holds for [`CASES`] values of `generate` with growing sizes.
+///
+/// Panics with the seed and the smallest failing value found by shrinking the
+/// first failing one. Set `AOC_PROP_SEED` to try other values than the default ones.
+pub fn check<T, G, P>(generate: G, property: P)
+where
+    T: Debug,
+    G: Fn(&mut Gen) -> T,
+    P: Fn(&T) -> bool,
+{
+    let seed = env::var("AOC_PROP_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(DEFAULT_SEED);
+
+    if let Some(failure) = find_failure(seed, CASES, generate, property) {
+        let Failure {
+            seed,
+            case,
+            shrinks,
+            value,
+        } = failure;
+        panic!(
+            "property failed for seed {seed} in case {case}, \
+             smallest failing value after {shrinks} shrinks:\n{value:#?}"
+        );
+    }
+}
+
+/// Value for which a property doesn't hold
+#[derive(Debug)]
+pub struct Failure<T> {
+    /// Seed of the first case
+    pub seed: u64,
+    /// Case the value was found in, its seed is `seed + case`
+    pub case: usize,
+    /// Number of times the first failing value got smaller
+    pub shrinks: usize,
+    pub value: T,
+}
+
+/// Try `property` on `cases` values of `generate` with sizes growing up to
+/// [`MAX_SIZE`], returns the first failing value after shrinking it
+pub fn find_failure<T, G, P>(
+    seed: u64,
+    cases: usize,
+    generate: G,
+    property: P,
+) -> Option<Failure<T>>
+where
+    G: Fn(&mut Gen) -> T,
+    P: Fn(&T) -> bool,
+{
+    for case in 0..cases {
+        let size = 1 + case * MAX_SIZE / cases;
+        let mut gen = Gen::recording(seed.wrapping_add(case as u64), size);
+        let value = generate(&mut gen);
+        if property(&value) {
+            continue;
+        }
+
+        let (value, shrinks) = shrink(gen.choices, size, &generate, &property);
+        return Some(Failure {
+            seed,
+            case,
+            shrinks,
+            value,
+        });
+    }
+    None
+}
+
+/// Smallest failing value derived from the choices of a failing one,
+/// and the number of times it got smaller
+fn shrink<T, G, P>(mut choices: Vec<u64>, size: usize, generate: G, property: P) -> (T, usize)
+where
+    G: Fn(&mut Gen) -> T,
+    P: Fn(&T) -> bool,
+{
+    let mut tries = 0;
+    let mut shrinks = 0;
+    // replays the candidate, returns its choices if the property fails and they are smaller
+    let fails = |candidate: Vec<u64>, current: &[u64], tries: &mut usize| {
+        *tries += 1;
+        let mut gen = Gen::replaying(candidate, size);
+        let value = generate(&mut gen);
+        let smaller = (gen.choices.len(), &gen.choices[..]) < (current.len(), current);
+        (smaller && !property(&value)).then_some(gen.choices)
+    };
+
+    let mut improved = true;
+    while improved && tries < MAX_SHRINKS {
+        improved = false;
+
+        // drop chunks of choices, e.g. whole elements of lists
+        for len in [8, 4, 2, 1] {
+            let mut i = 0;
+            while i + len <= choices.len() && tries < MAX_SHRINKS {
+                let mut candidate = choices.clone();
+                candidate.drain(i..i + len);
+                match fails(candidate, &choices, &mut tries) {
+                    Some(smaller) => {
+                        choices = smaller;
+                        shrinks += 1;
+                        improved = true;
+                    }
+                    None => i += 1,
+                }
+            }
+        }
+
+        // make single choices smaller
+        let mut i = 0;
+        while i < choices.len() && tries < MAX_SHRINKS {
+            let choice = choices[i];
+            let smaller = [0, choice / 2, choice.saturating_sub(1)]
+                .into_iter()
+                .filter(|smaller| *smaller < choice)
+                .find_map(|smaller| {
+                    let mut candidate = choices.clone();
+                    candidate[i] = smaller;
+                    fails(candidate, &choices, &mut tries)
+                });
+            match smaller {
+                Some(smaller) => {
+                    choices = smaller;
+                    shrinks += 1;
+                    improved = true;
+                }
+                None => i += 1,
+            }
+        }
+    }
+
+    let value = generate(&mut Gen::replaying(choices, size));
+    (value, shrinks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::Result,
+        solution::{Example, Runner, Solution},
+    };
+
+    #[test]
+    fn same_seed_same_values() {
+        let values = |seed| {
+            let mut gen = Gen::new(seed, 10);
+            gen.list(0, |gen| gen.range(1..=6))
+        };
+        assert_eq!(values(7), values(7));
+        assert_ne!(values(7), values(8));
+        assert!(values(7).iter().all(|value| (1..=6).contains(value)));
+    }
+
+    #[test]
+    fn days_without_generate_have_no_generator() {
+        struct Empty;
+        impl Solution for Empty {
+            type Input = ();
+            const YEAR: u32 = 2022;
+            const DAY: u32 = 25;
+            const TITLE: &'static str = "";
+            const EXAMPLES: &'static [Example] = &[];
+            fn parse(&self, _input: &str) -> Result<()> {
+                Ok(())
+            }
+            fn part1(&self, _input: &()) -> Result<String> {
+                Ok(String::from(""))
+            }
+            fn part2(&self, _input: &()) -> Result<String> {
+                Ok(String::from(""))
+            }
+        }
+
+        assert!(Runner::generator(&Empty).is_none());
+    }
+
+    #[test]
+    fn shrinks_to_smallest_counterexample() {
+        let generate = |gen: &mut Gen| gen.list(1, |gen| gen.range(0..=1000));
+        let property = |list: &Vec<usize>| list.iter().sum::<usize>() < 100;
+        let mut gen = Gen::recording(1, 20);
+        assert!(!property(&generate(&mut gen)));
+
+        let (smallest, shrinks) = shrink(gen.choices, 20, generate, property);
+        assert!(shrinks > 0);
+        assert_eq!(smallest, [100]);
+    }
+
+    #[test]
+    #[should_panic(expected = "shrinks:\n90")]
+    fn check_reports_failures() {
+        check(|gen| gen.range(0..=100), |n| *n < 90);
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+use crate::error::Result;
+
+/// Parsed input of a day that can be explored with day specific commands
+pub trait Session {
+    /// Usage of every command with a short description, shown by `help`
+    fn commands(&self) -> &'static [(&'static str, &'static str)];
+
+    /// Run a command with its arguments, returns the text to print
+    /// or a message describing what went wrong
+    fn execute(&mut self, command: &str, args: &[&str]) -> std::result::Result<String, String>;
+}
+
+/// Creates the session of a day from its puzzle input,
+/// see [`Solution::session`](crate::solution::Solution::session)
+pub type Open = fn(&str) -> Result<Box<dyn Session>>;
+
+/// Commands available for every day
+const BUILTIN: &[(&str, &str)] = &[
+    ("help", "List the commands"),
+    ("history", "List the commands entered so far"),
+    (
+        "!<N>",
+        "Run command N of the history again, !! runs the last one",
+    ),
+    ("quit", "Leave the REPL"),
+];
+
+/// Run the commands read from `input` until it ends or `quit` is entered,
+/// printing `prompt` before reading each one
+pub fn run<R, W>(
+    session: &mut dyn Session,
+    prompt: &str,
+    input: R,
+    output: &mut W,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    let mut history: Vec<String> = vec![];
+    let mut lines = input.lines();
+
+    loop {
+        write!(output, "{prompt}")?;
+        output.flush()?;
+        let Some(line) = lines.next() else {
+            writeln!(output)?;
+            return Ok(());
+        };
+        let line = line?;
+
+        let command = match expand(line.trim(), &history) {
+            Ok(command) if command.is_empty() => continue,
+            Ok(command) => command,
+            Err(message) => {
+                writeln!(output, "error: {message}")?;
+                continue;
+            }
+        };
+        // show what a history reference stands for
+        if command != line.trim() {
+            writeln!(output, "{command}")?;
+        }
+        history.push(command.clone());
+
+        let words = command.split_whitespace().collect::<Vec<_>>();
+        let reply = match words[0] {
+            "quit" | "exit" => return Ok(()),
+            "help" => Ok(help(session)),
+            "history" => Ok(history
+                .iter()
+                .enumerate()
+                .map(|(i, command)| format!("{:>4}  {command}\n", i + 1))
+                .collect()),
+            command => session.execute(command, &words[1..]),
+        };
+
+        match reply {
+            Ok(text) if text.is_empty() || text.ends_with('\n') => write!(output, "{text}")?,
+            Ok(text) => writeln!(output, "{text}")?,
+            Err(message) => writeln!(output, "error: {message}")?,
+        }
+    }
+}
+
+/// The command a line stands for, resolving references to the history
+fn expand(line: &str, history: &[String]) -> std::result::Result<String, String> {
+    let Some(reference) = line.strip_prefix('!') else {
+        return Ok(line.to_string());
+    };
+
+    let index = match reference {
+        "!" => history.len(),
+        _ => reference
+            .parse::<usize>()
+            .map_err(|_| format!("invalid history reference '{line}'"))?,
+    };
+    index
+        .checked_sub(1)
+        .and_then(|i| history.get(i))
+        .cloned()
+        .ok_or(format!("no command {index} in the history"))
+}
+
+fn help(session: &dyn Session) -> String {
+    let commands = session.commands().iter().chain(BUILTIN);
+    let width = commands
+        .clone()
+        .map(|(usage, _)| usage.len())
+        .max()
+        .unwrap_or(0);
+    commands
+        .map(|(usage, description)| format!("  {usage:width$}  {description}\n"))
+        .collect()
+}
+
+/// Error message for a command the session doesn't know
+pub fn unknown(command: &str) -> String {
+    format!("unknown command '{command}', try help")
+}
+
+/// Argument `i` of a command, `what` describes it in error messages
+pub fn arg<T: FromStr>(args: &[&str], i: usize, what: &str) -> std::result::Result<T, String> {
+    let arg = args.get(i).ok_or(format!("missing {what}"))?;
+    arg.parse().map_err(|_| format!("invalid {what} '{arg}'"))
+}
+
+/// Optional argument `i` of a command, none if it is missing
+pub fn optional_arg<T: FromStr>(
+    args: &[&str],
+    i: usize,
+    what: &str,
+) -> std::result::Result<Option<T>, String> {
+    match args.get(i) {
+        Some(_) => arg(args, i, what).map(Some),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Session adding up numbers
+    struct Counter {
+        total: i64,
+    }
+
+    impl Session for Counter {
+        fn commands(&self) -> &'static [(&'static str, &'static str)] {
+            &[("add <N>", "Add N to the total")]
+        }
+
+        fn execute(&mut self, command: &str, args: &[&str]) -> std::result::Result<String, String> {
+            match command {
+                "add" => {
+                    self.total += arg::<i64>(args, 0, "number")?;
+                    Ok(self.total.to_string())
+                }
+                _ => Err(unknown(command)),
+            }
+        }
+    }
+
+    fn run_lines(input: &str) -> String {
+        let mut output = vec![];
+        run(
+            &mut Counter { total: 0 },
+            "> ",
+            input.as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn commands_and_history() {
+        let output = run_lines("add 2\n\nadd x\n!!\n!1\nhistory\nquit\nadd 1\n");
+        let expected = [
+            "> 2\n",
+            "> ",
+            "> error: invalid number 'x'\n",
+            "> add x\nerror: invalid number 'x'\n",
+            "> add 2\n4\n",
+            ">    1  add 2\n   2  add x\n   3  add x\n   4  add 2\n   5  history\n",
+            "> ",
+        ];
+        assert_eq!(output, expected.concat());
+    }
+
+    #[test]
+    fn unknown_commands_and_references() {
+        let output = run_lines("sub 1\n!3\nhelp\n");
+        assert!(output.contains("error: unknown command 'sub', try help\n"));
+        assert!(output.contains("error: no command 3 in the history\n"));
+        assert!(output.contains("  add <N>  Add N to the total\n"));
+        // the input ended without quit
+        assert!(output.ends_with("> \n"));
+    }
+}
@@ -0,0 +1,152 @@
+use std::{fmt::Write, time::Duration};
+
+use crate::{
+    answers::Answers,
+    bench::format_duration,
+    output::{Record, Status},
+    solution::{Part, Runner},
+};
+
+/// Progress of a single day
+pub struct Row {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    /// Parts with a known correct answer
+    pub stars: usize,
+    /// Whether the answers of the executed parts match the known ones,
+    /// none if nothing was executed or an answer is not known yet
+    pub verified: Option<bool>,
+    /// Time to parse and solve each part, none if it was not executed or failed
+    pub times: [Option<Duration>; 2],
+}
+
+/// One row per day, `records` are the verified results of the executed parts
+/// and may be empty if the days were not executed
+pub fn rows(days: &[&dyn Runner], records: &[Record], answers: &Answers) -> Vec<Row> {
+    days.iter()
+        .map(|day| {
+            let (year, day_number) = (day.year(), day.day());
+            let records = records
+                .iter()
+                .filter(|r| (r.year, r.day) == (year, day_number))
+                .collect::<Vec<_>>();
+
+            let failed = records
+                .iter()
+                .any(|r| r.status != Status::Pass && r.status != Status::New);
+            let unknown = records.is_empty() || records.iter().any(|r| r.status == Status::New);
+            let verified = match (failed, unknown) {
+                (true, _) => Some(false),
+                (false, true) => None,
+                (false, false) => Some(true),
+            };
+            let time = |part| {
+                records
+                    .iter()
+                    .find(|r| r.part == part && r.answer.is_some())
+                    .map(|r| r.duration())
+            };
+
+            Row {
+                year,
+                day: day_number,
+                title: day.title(),
+                stars: [Part::One, Part::Two]
+                    .iter()
+                    .filter(|part| answers.is_solved(year, day_number, **part))
+                    .count(),
+                verified,
+                times: [time(Part::One), time(Part::Two)],
+            }
+        })
+        .collect()
+}
+
+/// Markdown table of the rows with a total after the last day of every year
+pub fn markdown(rows: &[Row]) -> String {
+    let mut table = String::from(
+        "| Year | Day | Title | Stars | Verified | Part 1 | Part 2 |\n\
+         |-----:|----:|-------|-------|:--------:|-------:|-------:|\n",
+    );
+    let time = |time: Option<Duration>| time.map_or(String::from(""), format_duration);
+    let bold = |text: String| match text.is_empty() {
+        true => text,
+        false => format!("**{text}**"),
+    };
+
+    for (i, row) in rows.iter().enumerate() {
+        let verified = match row.verified {
+            Some(true) => "✓",
+            Some(false) => "✗",
+            None => "",
+        };
+        let _ = writeln!(
+            table,
+            "| {} | {} | {} | {} | {verified} | {} | {} |",
+            row.year,
+            row.day,
+            row.title.replace('|', "\\|"),
+            "⭐".repeat(row.stars),
+            time(row.times[0]),
+            time(row.times[1])
+        );
+
+        if rows.get(i + 1).is_none_or(|next| next.year != row.year) {
+            let year = rows
+                .iter()
+                .filter(|r| r.year == row.year)
+                .collect::<Vec<_>>();
+            let total = |part: usize| {
+                let times = year.iter().filter_map(|r| r.times[part]);
+                times.clone().next().map(|_| times.sum())
+            };
+            let _ = writeln!(
+                table,
+                "| **{}** | | **Total** | **{}** ⭐ | | {} | {} |",
+                row.year,
+                year.iter().map(|r| r.stars).sum::<usize>(),
+                bold(time(total(0))),
+                bold(time(total(1)))
+            );
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(year: u32, day: u32, stars: usize, verified: Option<bool>, micros: u64) -> Row {
+        Row {
+            year,
+            day,
+            title: "A | B",
+            stars,
+            verified,
+            times: [Some(Duration::from_micros(micros)), None],
+        }
+    }
+
+    #[test]
+    fn markdown_has_year_totals() {
+        let table = markdown(&[
+            row(2021, 1, 2, Some(true), 5),
+            row(2022, 1, 2, Some(false), 10),
+            row(2022, 2, 1, None, 20),
+        ]);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[2], "| 2021 | 1 | A \\| B | ⭐⭐ | ✓ | 5.0µs |  |");
+        assert_eq!(
+            lines[3],
+            "| **2021** | | **Total** | **2** ⭐ | | **5.0µs** |  |"
+        );
+        assert_eq!(lines[5], "| 2022 | 2 | A \\| B | ⭐ |  | 20.0µs |  |");
+        assert_eq!(
+            lines[6],
+            "| **2022** | | **Total** | **3** ⭐ | | **30.0µs** |  |"
+        );
+    }
+}
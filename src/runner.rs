@@ -0,0 +1,162 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc::{self, AllocStats},
+    answers::{input_hash, HashingReader},
+    error::{read_input, stream_input, Error, Result},
+    pool,
+    solution::{Part, Runner, SolveReader},
+};
+
+/// Result of running all selected parts of a day
+pub struct DayResult {
+    pub day: &'static dyn Runner,
+    pub filename: String,
+    /// [`input_hash`] of the puzzle input the parts were solved with, only if they were
+    pub input_hash: Option<u64>,
+    /// Error reading or parsing the input, no parts were solved in that case
+    pub error: Option<Error>,
+    pub parts: Vec<PartResult>,
+}
+
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Result<String>,
+    /// Zero if the part was solved straight from the input file
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// Allocations while parsing and solving, only if counting is enabled
+    pub alloc: Option<AllocStats>,
+}
+
+/// Solve the given parts of all days on up to `jobs` threads (0 for one per CPU).
+/// Every day is an independent job reading and parsing its input once for all parts,
+/// or streaming it once per part if the day can, see [`Runner::streaming`].
+/// The results are returned in the order of `days`.
+pub fn execute<F>(
+    days: &[&'static dyn Runner],
+    parts: &[Part],
+    input_file: F,
+    jobs: usize,
+) -> Vec<DayResult>
+where
+    F: Fn(&dyn Runner) -> String,
+{
+    let days = days
+        .iter()
+        .map(|day| (*day, input_file(*day)))
+        .collect::<Vec<_>>();
+
+    pool::parallel_map(&days, pool::threads(jobs), |(day, filename)| {
+        let solved = match day.streaming() {
+            Some(solve) => stream_parts(solve, filename, parts),
+            None => parse_parts(*day, filename, parts),
+        };
+        let (input_hash, error, parts) = match solved {
+            Ok((hash, parts)) => (Some(hash), None, parts),
+            Err(err) => (None, Some(err), vec![]),
+        };
+        DayResult {
+            day: *day,
+            filename: filename.clone(),
+            input_hash,
+            error,
+            parts,
+        }
+    })
+}
+
+/// Parse the input once and solve every part with it,
+/// returns the hash of the input and the results of the parts
+fn parse_parts(day: &dyn Runner, filename: &str, parts: &[Part]) -> Result<(u64, Vec<PartResult>)> {
+    let input = read_input(filename)?;
+    let ((parsed, parse_time), parse_alloc) = counted(|| timed(|| day.parse(&input)));
+    let parsed = parsed?;
+
+    let results = parts
+        .iter()
+        .map(|part| {
+            let ((answer, solve_time), alloc) =
+                counted(|| timed(|| day.solve(parsed.as_ref(), *part)));
+            PartResult {
+                part: *part,
+                answer,
+                parse_time,
+                solve_time,
+                // the parsed input is allocated while solving
+                alloc: parse_alloc
+                    .zip(alloc)
+                    .map(|(parse, solve)| parse.then(solve)),
+            }
+        })
+        .collect();
+    Ok((input_hash(&input), results))
+}
+
+/// Solve every part reading the input on its own, the input is hashed
+/// in the same pass so the answers belong to the hashed content
+fn stream_parts(
+    solve: SolveReader,
+    filename: &str,
+    parts: &[Part],
+) -> Result<(u64, Vec<PartResult>)> {
+    let mut hash = None;
+    let mut results = vec![];
+    for part in parts {
+        // the buffer of the reader is allocated for solving too
+        let (streamed, alloc) = counted(|| {
+            stream_input(filename, |reader| {
+                let mut reader = HashingReader::new(reader);
+                let (answer, solve_time) = timed(|| solve(&mut reader, *part));
+                match answer {
+                    // an unreadable or invalid input is an error of the day,
+                    // like it is when parsing fails
+                    Err(err @ (Error::Io { .. } | Error::Parse { .. })) => Err(err),
+                    answer => Ok((answer, solve_time, reader.finish()?)),
+                }
+            })
+        });
+        let (answer, solve_time, part_hash) = streamed?;
+        if *hash.get_or_insert(part_hash) != part_hash {
+            return Err(Error::Io {
+                path: filename.to_string(),
+                source: io::Error::other("the input changed while it was solved"),
+            });
+        }
+
+        results.push(PartResult {
+            part: *part,
+            answer,
+            parse_time: Duration::ZERO,
+            solve_time,
+            alloc,
+        });
+    }
+
+    let hash = match hash {
+        Some(hash) => hash,
+        None => stream_input(filename, |reader| HashingReader::new(reader).finish())?,
+    };
+    Ok((hash, results))
+}
+
+/// Run `f`, measuring how long it takes
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Run `f`, counting its allocations if counting is enabled
+fn counted<T, F: FnOnce() -> T>(f: F) -> (T, Option<AllocStats>) {
+    match alloc::is_enabled() {
+        true => {
+            let (result, stats) = alloc::measure(f);
+            (result, Some(stats))
+        }
+        false => (f(), None),
+    }
+}
@@ -0,0 +1,306 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use crate::error::{Error, Result};
+
+const TEMPLATE: &str = "\
+use crate::{
+    error::{read_input, Error, Result},
+    solution::{example_tests, Example, Solution},
+};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input = Vec<String>;
+
+    const YEAR: u32 = super::YEAR;
+    const DAY: u32 = {day};
+    const TITLE: &'static str = {title};
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: None,
+        part2: None,
+    }];
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<String> {
+        Ok(solve_part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<String> {
+        Ok(solve_part2(input)?.to_string())
+    }
+}
+
+const EXAMPLE: &str = \"\\
+\";
+
+pub fn part1(filename: &str) -> Result<usize> {
+    read_input(filename).and_then(|content| part1_str(&content))
+}
+
+pub fn part1_str(input: &str) -> Result<usize> {
+    solve_part1(&parse_input(input)?)
+}
+
+pub fn part2(filename: &str) -> Result<usize> {
+    read_input(filename).and_then(|content| part2_str(&content))
+}
+
+pub fn part2_str(input: &str) -> Result<usize> {
+    solve_part2(&parse_input(input)?)
+}
+
+fn parse_input(content: &str) -> Result<Vec<String>> {
+    Ok(content.lines().map(String::from).collect())
+}
+
+fn solve_part1(_lines: &[String]) -> Result<usize> {
+    Err(Error::no_solution(\"part 1 is not solved yet\"))
+}
+
+fn solve_part2(_lines: &[String]) -> Result<usize> {
+    Err(Error::no_solution(\"part 2 is not solved yet\"))
+}
+
+example_tests!(Day{day});
+";
+
+const YEAR_TEMPLATE: &str = "\
+//! Advent of Code {year}
+
+pub const YEAR: u32 = {year};
+
+";
+
+/// Create the module skeleton and an empty input file for a new day
+/// and register the day (and its year if it is the first one) in `lib.rs`.
+/// Nothing is written if any of the files already exists.
+///
+/// Returns the paths of all created or modified files.
+pub fn new_day(root: &Path, year: u32, day: u32, title: &str) -> Result<Vec<String>> {
+    let module = root.join(format!("src/y{year}/day{day:02}.rs"));
+    let year_module = root.join(format!("src/y{year}/mod.rs"));
+    let input = root.join(crate::input_file(year, day));
+    let lib = root.join("src/lib.rs");
+
+    for path in [&module, &input] {
+        if path.exists() {
+            return Err(io_error(
+                path,
+                io::Error::new(ErrorKind::AlreadyExists, "refusing to overwrite"),
+            ));
+        }
+    }
+
+    let mut lib_content = read(&lib)?;
+    let new_year = !year_module.exists();
+    let year_content = match new_year {
+        true => {
+            lib_content = insert_sorted(
+                &lib_content,
+                &format!("pub mod y{year};"),
+                |line| {
+                    Some((
+                        line.strip_prefix("pub mod y")?
+                            .strip_suffix(';')?
+                            .parse()
+                            .ok()?,
+                        0,
+                    ))
+                },
+                (year, 0),
+            );
+            YEAR_TEMPLATE.replace("{year}", &year.to_string())
+        }
+        false => read(&year_module)?,
+    };
+    let year_content = insert_sorted(
+        &year_content,
+        &format!("pub mod day{day:02};"),
+        |line| Some((year, day_number(line.strip_prefix("pub mod day")?)?)),
+        (year, day),
+    );
+    lib_content = insert_sorted(
+        &lib_content,
+        &format!("    &y{year}::day{day:02}::Day{day},"),
+        |line| {
+            let (y, d) = line.strip_prefix("    &y")?.split_once("::day")?;
+            Some((y.parse().ok()?, day_number(d)?))
+        },
+        (year, day),
+    );
+
+    // the title goes last, so text like "{day}" in it isn't replaced,
+    // and as a debug formatted string literal quotes and backslashes are escaped
+    let source = TEMPLATE
+        .replace("{day}", &day.to_string())
+        .replace("{title}", &format!("{title:?}"));
+
+    write(&module, &source)?;
+    write(&input, "")?;
+    write(&year_module, &year_content)?;
+    write(&lib, &lib_content)?;
+
+    Ok([module, input, year_module, lib]
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect())
+}
+
+/// Leading day number of a string like "07::Day7" or "07;"
+fn day_number(str: &str) -> Option<u32> {
+    let digits = str
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+    digits.parse().ok()
+}
+
+/// Insert `entry` into `content` right after the last line whose (year, day) key
+/// is smaller than `key`, so entries stay ordered by year and day.
+/// Without such a line the entry is put in front of the first keyed line,
+/// or appended if there is none.
+fn insert_sorted<K>(content: &str, entry: &str, key_of: K, key: (u32, u32)) -> String
+where
+    K: Fn(&str) -> Option<(u32, u32)>,
+{
+    let mut lines = content.lines().collect::<Vec<_>>();
+    let index = match lines
+        .iter()
+        .rposition(|line| key_of(line).is_some_and(|k| k < key))
+    {
+        Some(index) => index + 1,
+        None => lines
+            .iter()
+            .position(|line| key_of(line).is_some())
+            // keep attributes together with the item they belong to
+            .map(
+                |index| match index > 0 && lines[index - 1].starts_with("#[") {
+                    true => index - 1,
+                    false => index,
+                },
+            )
+            .unwrap_or(lines.len()),
+    };
+    lines.insert(index, entry);
+    lines.join("\n") + "\n"
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|err| io_error(path, err))
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| io_error(dir, err))?;
+    }
+    fs::write(path, content).map_err(|err| io_error(path, err))
+}
+
+fn io_error(path: &Path, source: io::Error) -> Error {
+    Error::Io {
+        path: path.display().to_string(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    const LIB: &str = "\
+pub mod solution;
+
+pub mod y2022;
+
+pub static REGISTRY: &[&dyn Runner] = &[
+    &y2022::day01::Day1,
+    &y2022::day08::Day8,
+];
+";
+
+    fn tree(name: &str) -> std::path::PathBuf {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        write(&root.join("src/lib.rs"), LIB).unwrap();
+        write(
+            &root.join("src/y2022/mod.rs"),
+            "pub const YEAR: u32 = 2022;\n\npub mod day01;\npub mod day08;\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn inserts_entries_in_order() {
+        let key_of = |line: &str| Some((2022, day_number(line.strip_prefix("day")?)?));
+        let content = "start\nday01\nday08\nend\n";
+        assert_eq!(
+            insert_sorted(content, "day05", key_of, (2022, 5)),
+            "start\nday01\nday05\nday08\nend\n"
+        );
+        assert_eq!(
+            insert_sorted(content, "day09", key_of, (2022, 9)),
+            "start\nday01\nday08\nday09\nend\n"
+        );
+        // in front of the first entry, together with its attribute
+        assert_eq!(
+            insert_sorted("a\n#[x]\nday03\n", "day02", key_of, (2022, 2)),
+            "a\nday02\n#[x]\nday03\n"
+        );
+        assert_eq!(
+            insert_sorted("a\n", "day02", key_of, (2022, 2)),
+            "a\nday02\n"
+        );
+    }
+
+    #[test]
+    fn creates_and_registers_days() {
+        let root = tree("new-day");
+        let title = "Quote \" and back\\slash {day}";
+        let created = new_day(&root, 2022, 5, title).unwrap();
+        assert_eq!(created.len(), 4);
+
+        let module = fs::read_to_string(root.join("src/y2022/day05.rs")).unwrap();
+        assert!(
+            module.contains("const TITLE: &'static str = \"Quote \\\" and back\\\\slash {day}\";")
+        );
+        assert!(module.contains("pub struct Day5;"));
+        assert_eq!(
+            fs::read_to_string(root.join("data/2022/05.txt")).unwrap(),
+            ""
+        );
+        let year = fs::read_to_string(root.join("src/y2022/mod.rs")).unwrap();
+        assert!(year.ends_with("pub mod day01;\npub mod day05;\npub mod day08;\n"));
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("Day1,\n    &y2022::day05::Day5,\n    &y2022::day08::Day8,"));
+
+        // existing days are never overwritten
+        assert!(matches!(
+            new_day(&root, 2022, 5, "Again"),
+            Err(Error::Io { .. })
+        ));
+
+        // a new year gets a module and its days go after the ones of earlier years
+        new_day(&root, 2023, 1, "Trebuchet?!").unwrap();
+        let year = fs::read_to_string(root.join("src/y2023/mod.rs")).unwrap();
+        assert_eq!(
+            year,
+            "//! Advent of Code 2023\n\npub const YEAR: u32 = 2023;\n\npub mod day01;\n"
+        );
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("pub mod y2022;\npub mod y2023;\n"));
+        assert!(lib.contains("Day8,\n    &y2023::day01::Day1,\n];"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
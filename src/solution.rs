@@ -0,0 +1,197 @@
+use std::{any::Any, fmt, io::BufRead};
+
+use crate::{
+    error::{Error, Result},
+    prop::Generate,
+    repl::Open,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Official example from the puzzle description with its expected answers
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+}
+
+/// Solves a part from a reader of the puzzle input, see [`Solution::streaming`]
+pub type SolveReader = fn(&mut dyn BufRead, Part) -> Result<String>;
+
+/// Solution for a single day of the calendar
+///
+/// The puzzle input is parsed once and then shared by both parts.
+pub trait Solution {
+    /// Parsed representation of the puzzle input
+    type Input: 'static;
+
+    const YEAR: u32;
+    const DAY: u32;
+    const TITLE: &'static str;
+    const EXAMPLES: &'static [Example];
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Result<String>;
+    fn part2(&self, input: &Self::Input) -> Result<String>;
+
+    /// Every structural problem of the input, found without solving it.
+    /// Only reports the first problem unless a day knows how to find all of them.
+    fn check(&self, input: &str) -> Vec<Error> {
+        self.parse(input).err().into_iter().collect()
+    }
+
+    /// Generator of random valid puzzle inputs, see [`crate::prop`].
+    /// None unless a day knows how to generate its input.
+    fn generator(&self) -> Option<Generate> {
+        None
+    }
+
+    /// Solver of a part reading the input a buffer at a time instead of parsing
+    /// all of it, so very large inputs fit in memory. None unless a day can stream
+    /// its input.
+    fn streaming(&self) -> Option<SolveReader> {
+        None
+    }
+
+    /// Opens a REPL session exploring the parsed input, see [`crate::repl`].
+    /// None unless a day has REPL commands.
+    fn session(&self) -> Option<Open> {
+        None
+    }
+}
+
+/// Object safe counterpart of [`Solution`] so days with different input types
+/// can be stored in the same registry.
+pub trait Runner: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    fn examples(&self) -> &'static [Example];
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Result<String>;
+    fn check(&self, input: &str) -> Vec<Error>;
+    fn generator(&self) -> Option<Generate>;
+    fn streaming(&self) -> Option<SolveReader>;
+    fn session(&self) -> Option<Open>;
+}
+
+impl<S> Runner for S
+where
+    S: Solution + Sync,
+{
+    fn year(&self) -> u32 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        S::EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Result<String> {
+        // parsed always comes from Runner::parse of the same day
+        let input = parsed.downcast_ref::<S::Input>().unwrap();
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+
+    fn check(&self, input: &str) -> Vec<Error> {
+        Solution::check(self, input)
+    }
+
+    fn generator(&self) -> Option<Generate> {
+        Solution::generator(self)
+    }
+
+    fn streaming(&self) -> Option<SolveReader> {
+        Solution::streaming(self)
+    }
+
+    fn session(&self) -> Option<Open> {
+        Solution::session(self)
+    }
+}
+
+/// Run all examples of a day that have an expected answer for the given part.
+/// Returns a description of every example that did not produce the expected answer.
+pub fn check_examples(day: &dyn Runner, part: Part) -> Vec<String> {
+    let mut failures = vec![];
+    for (i, example) in day.examples().iter().enumerate() {
+        let Some(expected) = example.expected(part) else {
+            continue;
+        };
+
+        let answer = day
+            .parse(example.input)
+            .and_then(|parsed| day.solve(parsed.as_ref(), part));
+        match answer {
+            Ok(answer) if answer == expected => (),
+            Ok(answer) => failures.push(format!(
+                "example {} part {part}: expected {expected}, got {answer}",
+                i + 1
+            )),
+            Err(err) => failures.push(format!("example {} part {part}: {err}", i + 1)),
+        }
+    }
+    failures
+}
+
+/// Generate tests checking both parts of a day against its examples
+macro_rules! example_tests {
+    ($solution:expr) => {
+        #[cfg(test)]
+        mod example_tests {
+            use super::*;
+            use crate::solution::{check_examples, Part};
+
+            #[test]
+            fn part1() {
+                let failures = check_examples(&$solution, Part::One);
+                assert!(failures.is_empty(), "{}", failures.join("\n"));
+            }
+
+            #[test]
+            fn part2() {
+                let failures = check_examples(&$solution, Part::Two);
+                assert!(failures.is_empty(), "{}", failures.join("\n"));
+            }
+        }
+    };
+}
+
+pub(crate) use example_tests;
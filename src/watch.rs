@@ -0,0 +1,65 @@
+use std::{fs, time::SystemTime};
+
+/// Polls files for modifications
+pub struct Watcher {
+    files: Vec<String>,
+    /// Modification time and length of every file when last checked,
+    /// none before the first check
+    last: Option<Vec<Option<(SystemTime, u64)>>>,
+}
+
+impl Watcher {
+    pub fn new(files: Vec<String>) -> Watcher {
+        Watcher { files, last: None }
+    }
+
+    pub fn files(&self) -> &[String] {
+        &self.files
+    }
+
+    /// Whether any file was modified, created or removed since the last check.
+    /// The first check always reports a change.
+    pub fn changed(&mut self) -> bool {
+        // the length catches quick edits within the resolution of the modification time
+        let current = self
+            .files
+            .iter()
+            .map(|file| {
+                let metadata = fs::metadata(file).ok()?;
+                Some((metadata.modified().ok()?, metadata.len()))
+            })
+            .collect::<Vec<_>>();
+
+        let changed = self.last.as_ref() != Some(&current);
+        self.last = Some(current);
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn detects_modifications() {
+        let file = env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        let path = file.display().to_string();
+        let _ = fs::remove_file(&file);
+        let mut watcher = Watcher::new(vec![path]);
+
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        fs::write(&file, "1\n").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        fs::write(&file, "1\n2\n").unwrap();
+        assert!(watcher.changed());
+
+        fs::remove_file(&file).unwrap();
+        assert!(watcher.changed());
+    }
+}
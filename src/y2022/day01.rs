@@ -0,0 +1,180 @@
+use std::io::BufRead;
+
+use itertools::Itertools;
+
+use crate::{
+    error::{stream_input, Error, Result},
+    parse::{self, LineReader},
+    prop::{Gen, Generate},
+    solution::{example_tests, Example, Part, Solution, SolveReader},
+};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Vec<u32>>;
+
+    const YEAR: u32 = super::YEAR;
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("24000"),
+        part2: Some("45000"),
+    }];
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_elves(input)
+    }
+
+    fn part1(&self, elves: &Self::Input) -> Result<String> {
+        Ok(top_n(elves, 1).to_string())
+    }
+
+    fn part2(&self, elves: &Self::Input) -> Result<String> {
+        Ok(top_n(elves, 3).to_string())
+    }
+
+    fn check(&self, input: &str) -> Vec<Error> {
+        parse::lines(input)
+            .filter(|line| !line.text.is_empty())
+            .filter_map(|line| line.parse::<u32>("calories").err())
+            .collect()
+    }
+
+    fn generator(&self) -> Option<Generate> {
+        Some(generate)
+    }
+
+    fn streaming(&self) -> Option<SolveReader> {
+        Some(solve_reader)
+    }
+}
+
+fn solve_reader(reader: &mut dyn BufRead, part: Part) -> Result<String> {
+    let n = match part {
+        Part::One => 1,
+        Part::Two => 3,
+    };
+    top_n_elves_reader(reader, n).map(|total| total.to_string())
+}
+
+const EXAMPLE: &str = "\
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+/// Return the total amount of calories of the top n elves carrying the most calories
+///
+/// # Arguments
+///
+/// * `filename` - Path to input file containing a list of calories each elf is carrying.
+///   Each line contains one integer corresponding to an amount of calories.
+///   An empty line in indicates the end for one elf and the next line will
+///   belong to the next elf.
+///
+/// * `n` - Number of elves
+pub fn top_n_elves(filename: &str, n: usize) -> Result<u64> {
+    stream_input(filename, |reader| top_n_elves_reader(reader, n))
+}
+
+pub fn top_n_elves_str(input: &str, n: usize) -> Result<u64> {
+    top_n_elves_reader(input.as_bytes(), n)
+}
+
+/// Same as [`top_n_elves`] but reads the input one line at a time,
+/// only the total of the current elf and the top n totals are kept
+pub fn top_n_elves_reader<R: BufRead>(reader: R, n: usize) -> Result<u64> {
+    let mut lines = LineReader::new(reader);
+    // largest totals first
+    let mut top: Vec<u64> = Vec::with_capacity(n + 1);
+    let mut elf: Option<u64> = None;
+    let mut add = |total: u64| {
+        let i = top.partition_point(|other| *other >= total);
+        if i < n {
+            top.insert(i, total);
+            top.truncate(n);
+        }
+    };
+
+    while let Some(line) = lines.next_line()? {
+        match (line.text.is_empty(), elf) {
+            (true, Some(total)) => {
+                add(total);
+                elf = None;
+            }
+            (true, None) => {}
+            (false, _) => {
+                let calories = line.parse::<u32>("calories")?;
+                elf = Some(elf.unwrap_or(0) + u64::from(calories));
+            }
+        }
+    }
+    if let Some(total) = elf {
+        add(total);
+    }
+
+    Ok(top.iter().sum())
+}
+
+/// Totals are added up as u64, the calories of a single elf may already exceed u32
+fn top_n(elves: &[Vec<u32>], n: usize) -> u64 {
+    elves
+        .iter()
+        .map(|elf| elf.iter().map(|calories| u64::from(*calories)).sum::<u64>())
+        // .sorted_desc() see below
+        .sorted()
+        .rev()
+        .take(n)
+        .sum::<u64>()
+}
+
+fn parse_elves(content: &str) -> Result<Vec<Vec<u32>>> {
+    parse::paragraphs(content)
+        .iter()
+        .map(|elf| elf.iter().map(|line| line.parse("calories")).collect())
+        .collect()
+}
+
+/// Random elves carrying 1 to 15 items of up to 60000 calories each
+pub fn generate(gen: &mut Gen) -> String {
+    gen.list(1, |gen| {
+        let items = gen.range(1..=15);
+        (0..items)
+            .map(|_| format!("{}\n", gen.range(1..=60000)))
+            .collect::<String>()
+    })
+    .join("\n")
+}
+
+example_tests!(Day1);
+
+// trait SortedDescIterator: Iterator {
+//     fn sorted_desc(self) -> IntoIter<Self::Item>
+//     where
+//         Self::Item: Ord;
+// }
+
+// impl<I> SortedDescIterator for I
+// where
+//     I: Iterator,
+// {
+//     fn sorted_desc(self) -> IntoIter<Self::Item>
+//     where
+//         Self::Item: Ord,
+//     {
+//         self.sorted().rev().collect::<Vec<_>>().into_iter()
+//     }
+// }
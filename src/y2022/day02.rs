@@ -0,0 +1,291 @@
+use std::io::BufRead;
+
+use crate::{
+    error::{stream_input, Error, Result},
+    parse::{self, LineReader, Span},
+    prop::{Gen, Generate},
+    solution::{example_tests, Example, Part, Solution, SolveReader},
+};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    // the meaning of the second column depends on the part,
+    // so it is kept as it is until the part is known
+    type Input = Vec<(Shape, Column)>;
+
+    const YEAR: u32 = super::YEAR;
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("15"),
+        part2: Some("12"),
+    }];
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse::lines(input)
+            .filter(|line| !line.text.trim().is_empty())
+            .map(row)
+            .collect()
+    }
+
+    fn part1(&self, rows: &Self::Input) -> Result<String> {
+        Ok(points(rows, true).to_string())
+    }
+
+    fn part2(&self, rows: &Self::Input) -> Result<String> {
+        Ok(points(rows, false).to_string())
+    }
+
+    fn check(&self, input: &str) -> Vec<Error> {
+        parse::lines(input)
+            .filter(|line| !line.text.trim().is_empty())
+            .flat_map(|line| match line.words()[..] {
+                [first, second] => vec![shape(first).err(), column(second).err()],
+                _ => vec![Some(line.error("expected two columns"))],
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn generator(&self) -> Option<Generate> {
+        Some(generate)
+    }
+
+    fn streaming(&self) -> Option<SolveReader> {
+        Some(solve_reader)
+    }
+}
+
+fn solve_reader(reader: &mut dyn BufRead, part: Part) -> Result<String> {
+    total_points_reader(reader, part == Part::One).map(|points| points.to_string())
+}
+
+const EXAMPLE: &str = "\
+A Y
+B X
+C Z
+";
+
+/// Return the total points of the rock paper scissors tournament if you are following
+/// the given set of moves.
+///
+/// Points for each round are awarded as following:
+/// Win = 6 points, Draw = 3 points, Loss = 0 points
+/// Rock = 1 point, Paper = 2 points, Scissors = 3 points
+///
+/// # Arguments
+///
+/// * `filename` - Path to input file containing a list of rock paper scissors moves.
+///   First column is what your opponent is (apparently) going to play:
+///   A = Rock, B = Paper, C = Scissors
+///
+///   (Part 1)
+///   Second column is what player 2 (you) is supposed to play:
+///   X = Rock, Y = Paper, Z = Scissors
+///
+///   (Part 2)
+///   Second column is the desired outcome for player 2 (you):
+///   X = Loss, Y = Draw, Z = Win
+///
+/// * `part1` - Treat second column like (Part 1) or (Part 2)
+pub fn total_points(filename: &str, part1: bool) -> Result<u64> {
+    stream_input(filename, |reader| total_points_reader(reader, part1))
+}
+
+pub fn total_points_str(input: &str, part1: bool) -> Result<u64> {
+    total_points_reader(input.as_bytes(), part1)
+}
+
+/// Same as [`total_points`] but reads the rounds one line at a time,
+/// empty lines are skipped
+pub fn total_points_reader<R: BufRead>(reader: R, part1: bool) -> Result<u64> {
+    let mut lines = LineReader::new(reader);
+    let mut points = 0;
+    while let Some(line) = lines.next_line()? {
+        if !line.text.trim().is_empty() {
+            points = add_points(points, round(row(line)?, part1));
+        }
+    }
+    Ok(points)
+}
+
+fn points(rows: &[(Shape, Column)], part1: bool) -> u64 {
+    rows.iter()
+        .fold(0, |points, row| add_points(points, round(*row, part1)))
+}
+
+/// Total points after the round, a long tournament scores more than fits in u32
+fn add_points(points: u64, round: Round) -> u64 {
+    points + u64::from(round.points())
+}
+
+/// Opponent's shape and the second column of a line
+fn row(line: Span) -> Result<(Shape, Column)> {
+    match line.words()[..] {
+        [first, second] => Ok((shape(first)?, column(second)?)),
+        _ => Err(line.error("expected two columns")),
+    }
+}
+
+/// Round of a row, the second column is your shape in part 1
+/// and the outcome you need in part 2
+fn round((p1_shape, column): (Shape, Column), part1: bool) -> Round {
+    let p2_shape = match part1 {
+        true => column.shape(),
+        false => p1_shape.for_outcome(&column.outcome()),
+    };
+    Round { p1_shape, p2_shape }
+}
+
+fn shape(span: Span) -> Result<Shape> {
+    Shape::from_str(span.text).ok_or_else(|| span.error(format!("invalid shape '{}'", span.text)))
+}
+
+fn column(span: Span) -> Result<Column> {
+    Column::from_str(span.text).ok_or_else(|| {
+        span.error(format!(
+            "invalid second column '{}', expected X, Y or Z",
+            span.text
+        ))
+    })
+}
+
+/// Second column of the strategy guide
+#[derive(Clone, Copy)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+impl Column {
+    fn from_str(str: &str) -> Option<Column> {
+        match str {
+            "X" => Some(Column::X),
+            "Y" => Some(Column::Y),
+            "Z" => Some(Column::Z),
+            _ => None,
+        }
+    }
+    fn shape(self) -> Shape {
+        match self {
+            Column::X => Shape::Rock,
+            Column::Y => Shape::Paper,
+            Column::Z => Shape::Scissors,
+        }
+    }
+    fn outcome(self) -> Outcome {
+        match self {
+            Column::X => Outcome::Loss,
+            Column::Y => Outcome::Draw,
+            Column::Z => Outcome::Win,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Outcome {
+    fn points(&self) -> u32 {
+        match self {
+            Outcome::Win => 6,
+            Outcome::Draw => 3,
+            Outcome::Loss => 0,
+        }
+    }
+}
+
+impl Shape {
+    fn vs(&self, other: &Shape) -> Outcome {
+        match (self, other) {
+            (Shape::Rock, Shape::Scissors) => Outcome::Win,
+            (Shape::Rock, Shape::Paper) => Outcome::Loss,
+            (Shape::Paper, Shape::Rock) => Outcome::Win,
+            (Shape::Paper, Shape::Scissors) => Outcome::Loss,
+            (Shape::Scissors, Shape::Paper) => Outcome::Win,
+            (Shape::Scissors, Shape::Rock) => Outcome::Loss,
+            (_, _) => Outcome::Draw,
+        }
+    }
+    fn points(&self) -> u32 {
+        match self {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+        }
+    }
+    fn from_str(str: &str) -> Option<Shape> {
+        match str {
+            "A" | "X" => Some(Shape::Rock),
+            "B" | "Y" => Some(Shape::Paper),
+            "C" | "Z" => Some(Shape::Scissors),
+            _ => None,
+        }
+    }
+    /// Returns the shape the other player has to choose for desired outcome
+    /// E.g. Rock.for_outcome(Win) will return Paper
+    fn for_outcome(&self, outcome: &Outcome) -> Shape {
+        match (self, outcome) {
+            (Shape::Rock, Outcome::Win) => Shape::Paper,
+            (Shape::Rock, Outcome::Loss) => Shape::Scissors,
+            (Shape::Paper, Outcome::Win) => Shape::Scissors,
+            (Shape::Paper, Outcome::Loss) => Shape::Rock,
+            (Shape::Scissors, Outcome::Win) => Shape::Rock,
+            (Shape::Scissors, Outcome::Loss) => Shape::Paper,
+            (_, _) => *self,
+        }
+    }
+}
+
+struct Round {
+    p1_shape: Shape,
+    p2_shape: Shape,
+}
+
+impl Round {
+    /// Points player 2 (you) will get for this round
+    fn points(&self) -> u32 {
+        self.p2_shape.vs(&self.p1_shape).points() + self.p2_shape.points()
+    }
+}
+
+/// Random rounds, any shape against any second column
+pub fn generate(gen: &mut Gen) -> String {
+    gen.list(1, |gen| {
+        format!(
+            "{} {}\n",
+            gen.pick(&["A", "B", "C"]),
+            gen.pick(&["X", "Y", "Z"])
+        )
+    })
+    .concat()
+}
+
+example_tests!(Day2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn totals_exceed_u32() {
+        let round = Round {
+            p1_shape: Shape::Rock,
+            p2_shape: Shape::Paper,
+        };
+        assert_eq!(add_points(u64::from(u32::MAX), round), 4294967303);
+    }
+}
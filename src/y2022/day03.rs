@@ -0,0 +1,273 @@
+use std::io::BufRead;
+
+use crate::{
+    error::{stream_input, Error, Result},
+    parse::{self, LineReader, Span},
+    prop::{Gen, Generate},
+    solution::{example_tests, Example, Part, Solution, SolveReader},
+};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    /// Items of both compartments of every rucksack as sets of priorities
+    type Input = Vec<(u64, u64)>;
+
+    const YEAR: u32 = super::YEAR;
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("157"),
+        part2: Some("70"),
+    }];
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        validate_rucksacks(input)?;
+        parse::lines(input)
+            .map(|line| compartments(line.text))
+            .collect()
+    }
+
+    fn part1(&self, rucksacks: &Self::Input) -> Result<String> {
+        Ok(rucksacks
+            .iter()
+            .map(|rucksack| shared_priority(*rucksack))
+            .sum::<u64>()
+            .to_string())
+    }
+
+    fn part2(&self, rucksacks: &Self::Input) -> Result<String> {
+        if !rucksacks.len().is_multiple_of(3) {
+            return Err(last_group_error(rucksacks.len()));
+        }
+        let mut sum = 0;
+        for (i, group) in rucksacks.chunks(3).enumerate() {
+            sum += badge(i, group)?;
+        }
+        Ok(sum.to_string())
+    }
+
+    fn check(&self, input: &str) -> Vec<Error> {
+        let mut problems = rucksack_problems(input);
+        problems.extend(whole_groups(input).err());
+        problems
+    }
+
+    fn generator(&self) -> Option<Generate> {
+        Some(generate)
+    }
+
+    fn streaming(&self) -> Option<SolveReader> {
+        Some(solve_reader)
+    }
+}
+
+fn solve_reader(reader: &mut dyn BufRead, part: Part) -> Result<String> {
+    let sum = match part {
+        Part::One => sum_priorities_reader(reader),
+        Part::Two => sum_group_priorities_reader(reader),
+    };
+    sum.map(|sum| sum.to_string())
+}
+
+const EXAMPLE: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+pub fn sum_priorities(filename: &str) -> Result<u64> {
+    stream_input(filename, sum_priorities_reader)
+}
+
+pub fn sum_priorities_str(input: &str) -> Result<u64> {
+    sum_priorities_reader(input.as_bytes())
+}
+
+/// Same as [`sum_priorities`] but reads the rucksacks one line at a time
+pub fn sum_priorities_reader<R: BufRead>(reader: R) -> Result<u64> {
+    let mut lines = LineReader::new(reader);
+    let mut sum = 0;
+    while let Some(line) = lines.next_line()? {
+        if let Some(problem) = line_problems(line).into_iter().next() {
+            return Err(problem);
+        }
+        sum += shared_priority(compartments(line.text)?);
+    }
+    Ok(sum)
+}
+
+pub fn sum_group_priorities(filename: &str) -> Result<u64> {
+    stream_input(filename, sum_group_priorities_reader)
+}
+
+pub fn sum_group_priorities_str(input: &str) -> Result<u64> {
+    sum_group_priorities_reader(input.as_bytes())
+}
+
+/// Same as [`sum_group_priorities`] but reads the rucksacks one line at a time,
+/// only the rucksacks of the current group are kept
+pub fn sum_group_priorities_reader<R: BufRead>(reader: R) -> Result<u64> {
+    let mut lines = LineReader::new(reader);
+    let mut group = Vec::with_capacity(3);
+    let mut sum = 0;
+    let mut rucksacks = 0;
+    while let Some(line) = lines.next_line()? {
+        if let Some(problem) = line_problems(line).into_iter().next() {
+            return Err(problem);
+        }
+        group.push(compartments(line.text)?);
+        rucksacks = line.line;
+        if group.len() == 3 {
+            sum += badge(rucksacks / 3 - 1, &group)?;
+            group.clear();
+        }
+    }
+
+    match group.is_empty() {
+        true => Ok(sum),
+        false => Err(last_group_error(rucksacks)),
+    }
+}
+
+/// Items of both compartments of a rucksack as sets of priorities,
+/// the bit of an item's priority is set if the compartment has the item
+fn compartments(rucksack: &str) -> Result<(u64, u64)> {
+    let items = |compartment: &str| {
+        compartment
+            .chars()
+            .try_fold(0u64, |items, item| Ok(items | 1 << priority(item)?))
+    };
+    let (first, second) = rucksack.split_at(rucksack.len() / 2);
+    Ok((items(first)?, items(second)?))
+}
+
+/// Priority of the item in both compartments, 0 if there is none
+fn shared_priority((first, second): (u64, u64)) -> u64 {
+    match first & second {
+        0 => 0,
+        shared => u64::from(shared.trailing_zeros()),
+    }
+}
+
+/// Priority of the item the rucksacks of the `index`th group have in common
+fn badge(index: usize, group: &[(u64, u64)]) -> Result<u64> {
+    let common = group.iter().fold(u64::MAX, |common, (first, second)| {
+        common & (first | second)
+    });
+    match common {
+        0 => Err(Error::no_solution(format!(
+            "no badge found in group {} (lines {}-{})",
+            index + 1,
+            index * 3 + 1,
+            index * 3 + 3
+        ))),
+        common => Ok(u64::from(common.trailing_zeros())),
+    }
+}
+
+/// Fails unless the rucksacks form whole groups of 3
+fn whole_groups(content: &str) -> Result<()> {
+    let lines = content.lines().count();
+    match lines.is_multiple_of(3) {
+        true => Ok(()),
+        false => Err(last_group_error(lines)),
+    }
+}
+
+/// Error for an input of `lines` rucksacks that don't form whole groups
+fn last_group_error(lines: usize) -> Error {
+    Error::parse(
+        lines + 1,
+        1,
+        format!(
+            "expected groups of 3 rucksacks, last group has {}",
+            lines % 3
+        ),
+    )
+}
+
+/// Fails with the first problem of the rucksacks, if there is one
+fn validate_rucksacks(content: &str) -> Result<()> {
+    match rucksack_problems(content).into_iter().next() {
+        Some(problem) => Err(problem),
+        None => Ok(()),
+    }
+}
+
+/// Rucksacks that can't be split into two compartments of the same size
+/// and items that are not letters
+fn rucksack_problems(content: &str) -> Vec<Error> {
+    parse::lines(content).flat_map(line_problems).collect()
+}
+
+fn line_problems(line: Span) -> Vec<Error> {
+    let mut problems = line
+        .chars()
+        .filter(|(_, c)| !c.is_ascii_alphabetic())
+        .map(|(item, c)| item.error(format!("invalid item '{c}'")))
+        .collect::<Vec<_>>();
+    let items = line.text.chars().count();
+    if !items.is_multiple_of(2) {
+        problems.push(line.error(format!(
+            "odd number of items ({items}) can't be split into two compartments"
+        )));
+    }
+    problems
+}
+
+fn priority(item: char) -> Result<u32> {
+    match item {
+        'a'..='z' => Ok(item as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Ok(item as u32 - 'A' as u32 + 27),
+        _ => Err(Error::no_solution(format!("item '{item}' has no priority"))),
+    }
+}
+
+/// Random rucksacks whose compartments share exactly one item,
+/// in groups with exactly one badge each
+pub fn generate(gen: &mut Gen) -> String {
+    const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    gen.list(1, |gen| {
+        let mut items = ITEMS.to_vec();
+        gen.shuffle(&mut items);
+        let (badge, others) = (items[0], &items[1..]);
+        (0..3)
+            .map(|i| {
+                // every rucksack lacks a third of the other items,
+                // so none of them is in all three
+                let mut allowed = others
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| j % 3 != i)
+                    .map(|(_, item)| *item)
+                    .collect::<Vec<_>>();
+                // and the compartments take their items from separate halves
+                gen.shuffle(&mut allowed);
+                let (pool1, pool2) = allowed.split_at(allowed.len() / 2);
+                let shared = pool1[0];
+
+                let len = gen.range(2..=16);
+                let mut first = vec![shared, badge];
+                let mut second = vec![shared];
+                first.extend((2..len).map(|_| *gen.pick(pool1)));
+                second.extend((1..len).map(|_| *gen.pick(pool2)));
+                gen.shuffle(&mut first);
+                gen.shuffle(&mut second);
+                format!(
+                    "{}{}\n",
+                    String::from_utf8_lossy(&first),
+                    String::from_utf8_lossy(&second)
+                )
+            })
+            .collect::<String>()
+    })
+    .concat()
+}
+
+example_tests!(Day3);
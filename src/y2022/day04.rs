@@ -0,0 +1,192 @@
+use std::io::BufRead;
+
+use crate::{
+    error::{stream_input, Error, Result},
+    parse::{self, LineReader, Pattern, Span},
+    prop::{Gen, Generate},
+    solution::{example_tests, Example, Part, Solution, SolveReader},
+};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<(SectionAssignment, SectionAssignment)>;
+
+    const YEAR: u32 = super::YEAR;
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("2"),
+        part2: Some("4"),
+    }];
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        assignment_pairs(input)
+    }
+
+    fn part1(&self, pairs: &Self::Input) -> Result<String> {
+        Ok(full_overlaps(pairs).to_string())
+    }
+
+    fn part2(&self, pairs: &Self::Input) -> Result<String> {
+        Ok(partial_overlaps(pairs).to_string())
+    }
+
+    fn check(&self, input: &str) -> Vec<Error> {
+        parse::lines(input)
+            .flat_map(|line| match PAIR.fields(line) {
+                Ok([start1, end1, start2, end2]) => vec![
+                    assignment(start1, end1).err(),
+                    assignment(start2, end2).err(),
+                ],
+                Err(err) => vec![Some(err)],
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn generator(&self) -> Option<Generate> {
+        Some(generate)
+    }
+
+    fn streaming(&self) -> Option<SolveReader> {
+        Some(solve_reader)
+    }
+}
+
+fn solve_reader(reader: &mut dyn BufRead, part: Part) -> Result<String> {
+    let sum = match part {
+        Part::One => sum_full_overlaps_reader(reader),
+        Part::Two => sum_partial_overlaps_reader(reader),
+    };
+    sum.map(|sum| sum.to_string())
+}
+
+const EXAMPLE: &str = "\
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+pub fn sum_full_overlaps(filename: &str) -> Result<usize> {
+    stream_input(filename, sum_full_overlaps_reader)
+}
+
+pub fn sum_full_overlaps_str(input: &str) -> Result<usize> {
+    sum_full_overlaps_reader(input.as_bytes())
+}
+
+/// Same as [`sum_full_overlaps`] but reads the pairs one line at a time
+pub fn sum_full_overlaps_reader<R: BufRead>(reader: R) -> Result<usize> {
+    count_pairs(reader, SectionAssignment::full_overlap)
+}
+
+pub fn sum_partial_overlaps(filename: &str) -> Result<usize> {
+    stream_input(filename, sum_partial_overlaps_reader)
+}
+
+pub fn sum_partial_overlaps_str(input: &str) -> Result<usize> {
+    sum_partial_overlaps_reader(input.as_bytes())
+}
+
+/// Same as [`sum_partial_overlaps`] but reads the pairs one line at a time
+pub fn sum_partial_overlaps_reader<R: BufRead>(reader: R) -> Result<usize> {
+    count_pairs(reader, SectionAssignment::partial_overlap)
+}
+
+/// Number of pairs read from `reader` for which `overlap` holds
+fn count_pairs<R, F>(reader: R, overlap: F) -> Result<usize>
+where
+    R: BufRead,
+    F: Fn(&SectionAssignment, &SectionAssignment) -> bool,
+{
+    let mut lines = LineReader::new(reader);
+    let mut count = 0;
+    while let Some(line) = lines.next_line()? {
+        let (first, second) = assignment_pair(line)?;
+        if overlap(&first, &second) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn full_overlaps(pairs: &[(SectionAssignment, SectionAssignment)]) -> usize {
+    pairs
+        .iter()
+        .filter(|pair| pair.0.full_overlap(&pair.1))
+        .count()
+}
+
+fn partial_overlaps(pairs: &[(SectionAssignment, SectionAssignment)]) -> usize {
+    pairs
+        .iter()
+        .filter(|pair| pair.0.partial_overlap(&pair.1))
+        .count()
+}
+
+const PAIR: Pattern = Pattern::new("<start>-<end>,<start>-<end>");
+
+fn assignment_pairs(content: &str) -> Result<Vec<(SectionAssignment, SectionAssignment)>> {
+    parse::lines(content).map(assignment_pair).collect()
+}
+
+fn assignment_pair(line: Span) -> Result<(SectionAssignment, SectionAssignment)> {
+    let [start1, end1, start2, end2] = PAIR.fields(line)?;
+    Ok((assignment(start1, end1)?, assignment(start2, end2)?))
+}
+
+fn assignment(start: Span, end: Span) -> Result<SectionAssignment> {
+    let (first, last) = (start.parse("section")?, end.parse("section")?);
+    match first <= last {
+        true => Ok(SectionAssignment::new(first, last)),
+        false => Err(start.error(format!("sections {first}-{last} end before they start"))),
+    }
+}
+
+pub struct SectionAssignment {
+    start: u32,
+    end: u32,
+}
+
+impl SectionAssignment {
+    /// Sections from `start` to `end`, both inclusive
+    pub fn new(start: u32, end: u32) -> SectionAssignment {
+        SectionAssignment { start, end }
+    }
+
+    pub fn full_overlap(&self, other: &SectionAssignment) -> bool {
+        (self.start >= other.start && self.end <= other.end)
+            || (other.start >= self.start && other.end <= self.end)
+    }
+
+    pub fn contains(&self, num: u32) -> bool {
+        num >= self.start && num <= self.end
+    }
+
+    pub fn overlaps(&self, other: &SectionAssignment) -> bool {
+        self.contains(other.start) || self.contains(other.end)
+    }
+
+    pub fn partial_overlap(&self, other: &SectionAssignment) -> bool {
+        self.overlaps(other) || other.overlaps(self)
+    }
+}
+
+/// Random pairs of section assignments between 1 and 99, overlapping or not
+pub fn generate(gen: &mut Gen) -> String {
+    gen.list(1, |gen| {
+        let start1 = gen.range(1..=99);
+        let end1 = gen.range(start1..=99);
+        let start2 = gen.range(1..=99);
+        let end2 = gen.range(start2..=99);
+        format!("{start1}-{end1},{start2}-{end2}\n")
+    })
+    .concat()
+}
+
+example_tests!(Day4);
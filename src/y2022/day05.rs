@@ -0,0 +1,470 @@
+use std::fmt;
+
+use crate::{
+    error::{read_input, Error, Result},
+    parse::{self, Pattern, Span},
+    prop::{Gen, Generate},
+    repl::{self, Session},
+    solution::{example_tests, Example, Solution},
+};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    /// Initial stacks and the movement instructions
+    type Input = (Vec<Stack>, Vec<Movement>);
+
+    const YEAR: u32 = super::YEAR;
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("CMZ"),
+        part2: Some("MCD"),
+    }];
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_procedure(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<String> {
+        Ok(rearranged_top_crates(input, false))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<String> {
+        Ok(rearranged_top_crates(input, true))
+    }
+
+    fn check(&self, input: &str) -> Vec<Error> {
+        let paragraphs = parse::paragraphs(input);
+        let (drawing, movements) = match sections(&paragraphs) {
+            Ok(sections) => sections,
+            Err(err) => return vec![err],
+        };
+
+        let mut problems = drawing_problems(drawing);
+        let stack_count = match parse_stacks(drawing) {
+            Ok(stacks) => stacks.len(),
+            Err(err) => {
+                problems.push(err);
+                usize::MAX
+            }
+        };
+        problems.extend(
+            movements
+                .iter()
+                .filter_map(|line| parse_movements(&[*line], stack_count).err()),
+        );
+        problems
+    }
+
+    fn generator(&self) -> Option<Generate> {
+        Some(generate)
+    }
+
+    fn session(&self) -> Option<repl::Open> {
+        Some(StacksSession::open)
+    }
+}
+
+// no line continuation here, the leading whitespace of the drawing is significant
+const EXAMPLE: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+pub fn top_crates(filename: &str, in_order: bool) -> Result<String> {
+    read_input(filename).and_then(|content| top_crates_str(&content, in_order))
+}
+
+pub fn top_crates_str(input: &str, in_order: bool) -> Result<String> {
+    Ok(rearranged_top_crates(&parse_procedure(input)?, in_order))
+}
+
+fn rearranged_top_crates(
+    (stacks, movements): &(Vec<Stack>, Vec<Movement>),
+    in_order: bool,
+) -> String {
+    let mut stacks = stacks.clone();
+    apply_movements(&mut stacks, movements, in_order);
+    stacks.top_items()
+}
+
+/// Initial stacks and movements of the puzzle input
+pub fn parse_procedure(content: &str) -> Result<(Vec<Stack>, Vec<Movement>)> {
+    let paragraphs = parse::paragraphs(content);
+    let (drawing, movements) = sections(&paragraphs)?;
+
+    let stacks = parse_stacks(drawing)?;
+    let movements = parse_movements(movements, stacks.len())?;
+    Ok((stacks, movements))
+}
+
+/// Lines of the stack drawing and lines of the movements
+fn sections<'a, 'b>(paragraphs: &'a [Vec<Span<'b>>]) -> Result<(&'a [Span<'b>], &'a [Span<'b>])> {
+    // the stack drawing is followed by an empty line and the movements
+    match paragraphs {
+        [] => Ok((&[], &[])),
+        [drawing] => Ok((drawing, &[])),
+        [drawing, movements] => Ok((drawing, movements)),
+        [_, _, extra, ..] => Err(extra[0].error("unexpected lines after the movements")),
+    }
+}
+
+/// Cells of the drawing that are neither empty nor a crate like "[A]"
+fn drawing_problems(drawing: &[Span]) -> Vec<Error> {
+    let Some((_, crates)) = drawing.split_last() else {
+        return vec![];
+    };
+
+    crates
+        .iter()
+        .flat_map(|line| line.fixed_width(4))
+        .filter_map(|column| {
+            let cell = column.text.trim_end();
+            let mut chars = cell.chars();
+            let is_crate = cell.len() == 3
+                && chars.next() == Some('[')
+                && chars.next().is_some_and(char::is_alphabetic)
+                && chars.next() == Some(']');
+            match cell.is_empty() || is_crate {
+                true => None,
+                false => Some(column.error(format!("expected a crate like '[A]', found '{cell}'"))),
+            }
+        })
+        .collect()
+}
+
+fn parse_stacks(drawing: &[Span]) -> Result<Vec<Stack>> {
+    // the last line of the drawing numbers the stacks in order
+    let Some((numbers, crates)) = drawing.split_last() else {
+        return Err(Error::parse(1, 1, "expected a line with the stack numbers"));
+    };
+    let count = numbers
+        .integers::<usize>()?
+        .last()
+        .copied()
+        .ok_or_else(|| numbers.error("expected a line with the stack numbers"))?;
+    // a stack takes 4 characters, the last one may lack the trailing space
+    let width = drawing
+        .iter()
+        .map(|line| line.text.len())
+        .max()
+        .unwrap_or(0);
+    if count > width.div_ceil(4) {
+        let last = numbers.words().last().copied().unwrap_or(*numbers);
+        return Err(last.error(format!(
+            "{count} stacks don't fit a drawing {width} characters wide"
+        )));
+    }
+
+    let mut stacks = vec![Stack::create(); count];
+    // read in reverse to push items from bottom up onto stack
+    for line in crates.iter().rev() {
+        // lines have the format "[A] [B] [C] ..." with a crate every 4 characters
+        for (stack, column) in stacks.iter_mut().zip(line.fixed_width(4)) {
+            match column.text.chars().nth(1) {
+                Some(c) if c.is_alphabetic() => stack.items.push(c),
+                _ => (),
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+const MOVEMENT: Pattern = Pattern::new("move <n> from <stack> to <stack>");
+
+fn parse_movements(lines: &[Span], stack_count: usize) -> Result<Vec<Movement>> {
+    lines
+        .iter()
+        .map(|line| {
+            let [n, from, to] = MOVEMENT.fields(*line)?;
+            let stack = |span: Span| {
+                span.parse::<usize>("stack")?
+                    .checked_sub(1)
+                    .filter(|index| *index < stack_count)
+                    .ok_or_else(|| span.error(format!("invalid stack '{}'", span.text)))
+            };
+
+            // zero based vector indices, input file starts at 1
+            Ok(Movement {
+                n: n.parse("number")?,
+                from: stack(from)?,
+                to: stack(to)?,
+            })
+        })
+        .collect()
+}
+
+/// Move the crates, one at a time or, `in_order`, several at once
+pub fn apply_movements(stacks: &mut Vec<Stack>, movements: &[Movement], in_order: bool) {
+    for mov in movements {
+        if in_order {
+            stacks.move_n_in_order(mov.n, mov.from, mov.to);
+        } else {
+            stacks.move_n(mov.n, mov.from, mov.to);
+        }
+    }
+}
+
+/// Move `n` crates between the stacks with the 0 based indices `from` and `to`
+pub struct Movement {
+    pub n: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// Same format as in the puzzle input
+impl fmt::Display for Movement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.n,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct Stack {
+    items: Vec<char>,
+}
+
+impl Stack {
+    pub fn create() -> Stack {
+        Stack { items: vec![] }
+    }
+
+    /// Stack with the given crates from bottom to top
+    pub fn from_items(items: &[char]) -> Stack {
+        Stack {
+            items: items.to_vec(),
+        }
+    }
+
+    /// Crates from bottom to top
+    pub fn items(&self) -> &[char] {
+        &self.items
+    }
+}
+
+pub trait StackMovement {
+    fn move_n(&mut self, n: usize, from: usize, to: usize);
+    fn move_n_in_order(&mut self, n: usize, from: usize, to: usize);
+}
+
+impl StackMovement for Vec<Stack> {
+    fn move_n(&mut self, n: usize, from: usize, to: usize) {
+        for _ in 0..n {
+            if let Some(item) = self[from].items.pop() {
+                self[to].items.push(item);
+            }
+        }
+    }
+
+    fn move_n_in_order(&mut self, n: usize, from: usize, to: usize) {
+        // store items in temporary stack (reverse order)
+        let mut temp = Stack::create();
+
+        for _ in 0..n {
+            if let Some(item) = self[from].items.pop() {
+                temp.items.push(item);
+            }
+        }
+
+        // reverse temporary stack to get items back in original order
+        while let Some(item) = temp.items.pop() {
+            self[to].items.push(item);
+        }
+    }
+}
+
+pub trait StackInspect {
+    fn top_items(&self) -> String;
+    fn drawing(&self) -> String;
+    fn print(&self);
+}
+
+impl StackInspect for Vec<Stack> {
+    fn top_items(&self) -> String {
+        self.iter().fold(String::from(""), |mut str, stack| {
+            if let Some(item) = stack.items.last() {
+                str.push(*item);
+            } else {
+                str.push(' ');
+            }
+            str
+        })
+    }
+
+    // visual representation of the stacks, one line per level from the top
+    fn drawing(&self) -> String {
+        let max_len = self
+            .iter()
+            .map(|stack| stack.items.len())
+            .max()
+            .unwrap_or(0);
+        let mut drawing = String::from("");
+        for i in (0..max_len).rev() {
+            for stack in self {
+                if stack.items.len() > i {
+                    drawing.push_str(&format!("[{}] ", stack.items[i]));
+                } else {
+                    drawing.push_str("    ");
+                }
+            }
+            drawing.push('\n');
+        }
+        drawing
+    }
+
+    fn print(&self) {
+        print!("{}", self.drawing());
+    }
+}
+
+/// REPL state of day 5, the stacks can be shown after any number of movements
+pub struct StacksSession {
+    stacks: Vec<Stack>,
+    movements: Vec<Movement>,
+    in_order: bool,
+}
+
+impl StacksSession {
+    pub fn open(input: &str) -> Result<Box<dyn Session>> {
+        let (stacks, movements) = parse_procedure(input)?;
+        Ok(Box::new(StacksSession {
+            stacks,
+            movements,
+            in_order: false,
+        }))
+    }
+
+    /// Stacks after the first `k` movements, all movements if none
+    fn after(&self, k: Option<usize>) -> std::result::Result<Vec<Stack>, String> {
+        let k = k.unwrap_or(self.movements.len());
+        if k > self.movements.len() {
+            return Err(format!("there are only {} movements", self.movements.len()));
+        }
+
+        let mut stacks = self.stacks.clone();
+        apply_movements(&mut stacks, &self.movements[..k], self.in_order);
+        Ok(stacks)
+    }
+}
+
+impl Session for StacksSession {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            (
+                "stacks [K]",
+                "Show the stacks after K movements (default: all)",
+            ),
+            ("top [K]", "Top crates after K movements (default: all)"),
+            (
+                "moves [FROM] [TO]",
+                "List the movements FROM to TO (default: all)",
+            ),
+            (
+                "crane <9000|9001>",
+                "Move one crate at a time (part 1) or several at once (part 2)",
+            ),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> std::result::Result<String, String> {
+        match command {
+            "stacks" => {
+                let stacks = self.after(repl::optional_arg(args, 0, "number of movements")?)?;
+                let numbers = (1..=stacks.len())
+                    .map(|i| format!(" {i}  "))
+                    .collect::<String>();
+                Ok(format!("{}{}", stacks.drawing(), numbers.trim_end()))
+            }
+            "top" => {
+                let stacks = self.after(repl::optional_arg(args, 0, "number of movements")?)?;
+                Ok(stacks.top_items())
+            }
+            "moves" => {
+                let from = repl::optional_arg(args, 0, "movement")?.unwrap_or(1);
+                let to = repl::optional_arg(args, 1, "movement")?.unwrap_or(self.movements.len());
+                Ok((from.max(1)..=to.min(self.movements.len()))
+                    .map(|k| format!("{k:>4}  {}\n", self.movements[k - 1]))
+                    .collect())
+            }
+            "crane" => {
+                self.in_order = match repl::arg::<u32>(args, 0, "crane")? {
+                    9000 => false,
+                    9001 => true,
+                    crane => return Err(format!("unknown crane {crane}, expected 9000 or 9001")),
+                };
+                Ok(String::from(""))
+            }
+            _ => Err(repl::unknown(command)),
+        }
+    }
+}
+
+/// Random drawing of 2 to 9 stacks with movements that only take crates a stack has
+pub fn generate(gen: &mut Gen) -> String {
+    let count = gen.range(2..=9);
+    let mut stacks = (0..count)
+        .map(|_| {
+            let height = gen.range(0..=8);
+            (0..height)
+                .map(|_| (b'A' + gen.range(0..=25) as u8) as char)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    if stacks.iter().all(Vec::is_empty) {
+        stacks[0].push('A');
+    }
+
+    let tallest = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut input = (0..tallest)
+        .rev()
+        .map(|level| {
+            let line = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(item) => format!("[{item}]"),
+                    None => String::from("   "),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!("{line}\n")
+        })
+        .collect::<String>();
+    let numbers = (1..=count).map(|i| format!(" {i} ")).collect::<Vec<_>>();
+    input.push_str(&numbers.join(" "));
+    input.push('\n');
+
+    // only move crates that are there, like the puzzle does
+    let mut heights = stacks.iter().map(Vec::len).collect::<Vec<_>>();
+    let movements = gen.list(0, |gen| {
+        let filled = (0..count).filter(|i| heights[*i] > 0).collect::<Vec<_>>();
+        let from = *gen.pick(&filled);
+        let others = (0..count).filter(|i| *i != from).collect::<Vec<_>>();
+        let to = *gen.pick(&others);
+        let n = gen.range(1..=heights[from]);
+        heights[from] -= n;
+        heights[to] += n;
+        format!("move {n} from {} to {}\n", from + 1, to + 1)
+    });
+    if !movements.is_empty() {
+        input.push('\n');
+        input.push_str(&movements.concat());
+    }
+    input
+}
+
+example_tests!(Day5);
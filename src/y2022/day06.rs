@@ -0,0 +1,277 @@
+use std::{collections::VecDeque, io::BufRead};
+
+use crate::{
+    error::{read_error, stream_input, Error, Result},
+    parse,
+    prop::{Gen, Generate},
+    solution::{example_tests, Example, Part, Solution, SolveReader},
+};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Markers;
+
+    const YEAR: u32 = super::YEAR;
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            part1: Some("7"),
+            part2: Some("19"),
+        },
+        Example {
+            input: "bvwbjplbgvbhsrlpgdmjqwftvncz",
+            part1: Some("5"),
+            part2: Some("23"),
+        },
+        Example {
+            input: "nppdvjthqldpwncqszvftbrmjlhg",
+            part1: Some("6"),
+            part2: Some("23"),
+        },
+        Example {
+            input: "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+            part1: Some("10"),
+            part2: Some("29"),
+        },
+        Example {
+            input: "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+            part1: Some("11"),
+            part2: Some("26"),
+        },
+    ];
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        if let Some(problem) = self.check(input).into_iter().next() {
+            return Err(problem);
+        }
+        // valid, so the only error left is a missing marker
+        Ok(Markers {
+            packet: chars_before_marker_str::<4>(input).ok(),
+            message: chars_before_marker_str::<14>(input).ok(),
+        })
+    }
+
+    fn part1(&self, markers: &Self::Input) -> Result<String> {
+        Ok(markers.packet.ok_or_else(no_marker::<4>)?.to_string())
+    }
+
+    fn part2(&self, markers: &Self::Input) -> Result<String> {
+        Ok(markers.message.ok_or_else(no_marker::<14>)?.to_string())
+    }
+
+    fn check(&self, input: &str) -> Vec<Error> {
+        let mut problems = vec![];
+        for line in parse::lines(input) {
+            if line.line == 2 {
+                problems.push(line.error("expected the datastream on a single line"));
+            }
+            problems.extend(
+                line.chars()
+                    .filter(|(_, c)| !c.is_ascii_lowercase())
+                    .map(|(span, c)| span.error(format!("invalid character '{c}'"))),
+            );
+        }
+        problems
+    }
+
+    fn generator(&self) -> Option<Generate> {
+        Some(generate)
+    }
+
+    fn streaming(&self) -> Option<SolveReader> {
+        Some(solve_reader)
+    }
+}
+
+fn solve_reader(reader: &mut dyn BufRead, part: Part) -> Result<String> {
+    let end = match part {
+        Part::One => chars_before_marker_reader::<4, _>(reader),
+        Part::Two => chars_before_marker_reader::<14, _>(reader),
+    };
+    end.map(|end| end.to_string())
+}
+
+/// Ends of the first start-of-packet and start-of-message markers, found
+/// while parsing so the datastream itself doesn't have to be kept
+pub struct Markers {
+    pub packet: Option<usize>,
+    pub message: Option<usize>,
+}
+
+pub fn chars_before_marker<const N: usize>(filename: &str) -> Result<usize> {
+    stream_input(filename, chars_before_marker_reader::<N, _>)
+}
+
+pub fn chars_before_marker_str<const N: usize>(input: &str) -> Result<usize> {
+    chars_before_marker_reader::<N, _>(input.as_bytes())
+}
+
+/// Same as [`chars_before_marker`] but reads the datastream a buffer at a time.
+///
+/// Remembers where every character was last seen instead of comparing the whole
+/// window like [`CharMarker`] does, which is kept as the reference. All of the
+/// input is validated like [`Day6::check`] does: the datastream is a single line
+/// of lowercase letters, so every byte of it is a character.
+pub fn chars_before_marker_reader<const N: usize, R: BufRead>(mut reader: R) -> Result<usize> {
+    let mut marker = LastSeen::<N>::new();
+    let mut end = None;
+    // column of the last byte of the datastream
+    let mut column = 0;
+    let mut line_ended = false;
+    let mut carriage_return = false;
+    loop {
+        let buffer = reader.fill_buf().map_err(read_error)?;
+        if buffer.is_empty() {
+            break;
+        }
+        for byte in buffer {
+            if line_ended {
+                return Err(Error::parse(
+                    2,
+                    1,
+                    "expected the datastream on a single line",
+                ));
+            }
+            if carriage_return && *byte != b'\n' {
+                return Err(invalid_byte(column, b'\r'));
+            }
+            match byte {
+                b'a'..=b'z' => {
+                    column += 1;
+                    if end.is_none() && marker.push(*byte) {
+                        end = Some(column);
+                    }
+                }
+                b'\n' => line_ended = true,
+                b'\r' => {
+                    column += 1;
+                    carriage_return = true;
+                }
+                byte => return Err(invalid_byte(column + 1, *byte)),
+            }
+        }
+        let len = buffer.len();
+        reader.consume(len);
+    }
+
+    match carriage_return && !line_ended {
+        true => Err(invalid_byte(column, b'\r')),
+        false => end.ok_or_else(no_marker::<N>),
+    }
+}
+
+/// Error about a byte of the datastream at the given column
+fn invalid_byte(column: usize, byte: u8) -> Error {
+    let message = match byte.is_ascii() {
+        true => format!("invalid character {:?}", char::from(byte)),
+        false => format!("invalid byte 0x{byte:02x}"),
+    };
+    Error::parse(1, column, message)
+}
+
+/// State of [`chars_before_marker_reader`], fed one byte of the datastream at a time
+struct LastSeen<const N: usize> {
+    /// One past the last position of every byte, 0 if it wasn't seen yet
+    seen: [usize; 256],
+    /// Start of the window without repeated characters ending at the last byte
+    start: usize,
+    /// Number of bytes pushed so far
+    len: usize,
+}
+
+impl<const N: usize> LastSeen<N> {
+    fn new() -> LastSeen<N> {
+        LastSeen {
+            seen: [0; 256],
+            start: 0,
+            len: 0,
+        }
+    }
+
+    /// Whether the last `N` bytes, ending with this one, are all different
+    fn push(&mut self, byte: u8) -> bool {
+        self.start = self.start.max(self.seen[byte as usize]);
+        self.len += 1;
+        self.seen[byte as usize] = self.len;
+        self.len - self.start == N
+    }
+}
+
+fn no_marker<const N: usize>() -> Error {
+    Error::no_solution(format!("no marker of {N} distinct characters found"))
+}
+
+/// Sliding window over the last `N` characters of a stream
+pub struct CharMarker<const N: usize> {
+    vec: VecDeque<char>,
+    total_chars: usize,
+}
+
+impl<const N: usize> CharMarker<N> {
+    pub fn create() -> CharMarker<N> {
+        CharMarker::<N> {
+            vec: VecDeque::new(),
+            total_chars: 0,
+        }
+    }
+
+    pub fn push(&mut self, c: char) {
+        if self.vec.len() == N {
+            self.vec.pop_front();
+        }
+        self.vec.push_back(c);
+        self.total_chars += 1;
+    }
+
+    /// Whether the last `N` characters are all different
+    pub fn is_present(&self) -> bool {
+        let unique = self.vec.iter().fold(vec![], |mut unique, c| {
+            if !unique.contains(c) {
+                unique.push(*c);
+            }
+            unique
+        });
+
+        self.vec.len() == N && unique.len() == N
+    }
+
+    /// Number of characters pushed so far
+    pub fn total_chars(&self) -> usize {
+        self.total_chars
+    }
+
+    /// Number of characters up to and including the first marker
+    pub fn find_index_after_marker(str: &str) -> Result<usize> {
+        let mut marker = CharMarker::<N>::create();
+        for c in str.chars() {
+            marker.push(c);
+            if marker.is_present() {
+                return Ok(marker.total_chars);
+            }
+        }
+
+        Err(no_marker::<N>())
+    }
+}
+
+/// Random datastream of lowercase letters with a marker of 14 distinct letters in it,
+/// so both parts have a solution
+pub fn generate(gen: &mut Gen) -> String {
+    let mut letters = ('a'..='z').collect::<Vec<_>>();
+    let mut input = gen
+        .list(0, |gen| *gen.pick(&letters))
+        .into_iter()
+        .collect::<String>();
+
+    // make sure there is a marker for both parts
+    gen.shuffle(&mut letters);
+    input.extend(&letters[..14]);
+    input.extend(gen.list(0, |gen| *gen.pick(&letters)));
+    input.push('\n');
+    input
+}
+
+example_tests!(Day6);
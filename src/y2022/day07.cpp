@@ -148,7 +148,7 @@ struct FileSystem
 
 int main()
 {
-    auto fs = FileSystem::parse_from_terminal("data/aoc7.txt");
+    auto fs = FileSystem::parse_from_terminal("data/2022/07.txt");
 
     auto dirs = fs.filter_dirs([](const std::shared_ptr<File> &f) { return f->total_size() < 100000; });
     auto sum = std::accumulate(dirs.cbegin(), dirs.cend(), 0,
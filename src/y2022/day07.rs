@@ -0,0 +1,511 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use crate::{
+    error::{read_input, Error, Result},
+    parse::{self, Pattern, Span},
+    prop::{Gen, Generate},
+    repl::{self, Session},
+    solution::{example_tests, Example, Solution},
+};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = FileSystem;
+
+    const YEAR: u32 = super::YEAR;
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("95437"),
+        part2: Some("24933642"),
+    }];
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        FileSystem::parse_from_terminal_output(input)
+    }
+
+    fn part1(&self, fs: &Self::Input) -> Result<String> {
+        Ok(fs_size_of_dirs_below(fs, 100000).to_string())
+    }
+
+    fn part2(&self, fs: &Self::Input) -> Result<String> {
+        Ok(fs_dir_to_delete(fs, 70000000, 30000000)?.to_string())
+    }
+
+    fn check(&self, input: &str) -> Vec<Error> {
+        FileSystem::replay(input).1
+    }
+
+    fn generator(&self) -> Option<Generate> {
+        Some(generate)
+    }
+
+    fn session(&self) -> Option<repl::Open> {
+        Some(ShellSession::open)
+    }
+}
+
+const EXAMPLE: &str = "\
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+pub fn size_of_dirs_below(filename: &str, below: usize) -> Result<usize> {
+    read_input(filename).and_then(|content| size_of_dirs_below_str(&content, below))
+}
+
+pub fn size_of_dirs_below_str(input: &str, below: usize) -> Result<usize> {
+    let fs = FileSystem::parse_from_terminal_output(input)?;
+    Ok(fs_size_of_dirs_below(&fs, below))
+}
+
+pub fn dir_to_delete(filename: &str, total_size: usize, required_size: usize) -> Result<usize> {
+    read_input(filename).and_then(|content| dir_to_delete_str(&content, total_size, required_size))
+}
+
+pub fn dir_to_delete_str(input: &str, total_size: usize, required_size: usize) -> Result<usize> {
+    let fs = FileSystem::parse_from_terminal_output(input)?;
+    fs_dir_to_delete(&fs, total_size, required_size)
+}
+
+fn fs_size_of_dirs_below(fs: &FileSystem, below: usize) -> usize {
+    // fs.size_infos()
+    //     .iter()
+    //     .filter(|size| size < &&below)
+    //     .fold(0, |sum, size| sum + size)
+
+    // fs.tree();
+
+    fs.filter_dirs(|f| File::total_size(f) < below)
+        .iter()
+        .fold(0, |sum, f| sum + File::total_size(f))
+}
+
+fn fs_dir_to_delete(fs: &FileSystem, total_size: usize, required_size: usize) -> Result<usize> {
+    let used = File::total_size(&fs.root);
+    let remaining = total_size.checked_sub(used).ok_or_else(|| {
+        Error::no_solution(format!("{used} used exceeds the disk size of {total_size}"))
+    })?;
+    let min_size = required_size.saturating_sub(remaining);
+
+    let dirs = fs.filter_dirs(|f| File::total_size(f) >= min_size);
+    let dir = dirs
+        .iter()
+        .min_by(|l, r| File::total_size(l).cmp(&File::total_size(r)))
+        .ok_or_else(|| Error::no_solution(format!("no directory frees up {min_size}")))?;
+
+    Ok(File::total_size(dir))
+}
+
+pub struct File {
+    name: String,
+    size: usize,
+    is_dir: bool,
+    files: Vec<Rc<RefCell<File>>>,
+    parent: Option<Weak<RefCell<File>>>,
+}
+
+impl File {
+    fn new(name: &str, parent: &Option<Weak<RefCell<File>>>) -> File {
+        File {
+            name: name.to_string(),
+            size: 0,
+            is_dir: true,
+            files: vec![],
+            parent: parent.clone(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+
+    /// Files of a directory
+    pub fn files(&self) -> &[Rc<RefCell<File>>] {
+        &self.files
+    }
+
+    pub fn info_str(&self) -> String {
+        if self.is_dir {
+            format!("{} (dir)", self.name)
+        } else {
+            format!("{} (file, size={})", self.name, self.size)
+        }
+    }
+
+    /// Size of a file or of everything inside a directory
+    pub fn total_size(file: &Rc<RefCell<File>>) -> usize {
+        if !file.borrow().is_dir {
+            return file.borrow().size;
+        }
+
+        File::total_size_impl(file, 0)
+    }
+
+    fn total_size_impl(current: &Rc<RefCell<File>>, current_size: usize) -> usize {
+        if !current.borrow().is_dir {
+            return current.borrow().size;
+        }
+
+        let mut size = current_size;
+        for f in &current.borrow().files {
+            size += File::total_size_impl(f, current_size);
+        }
+
+        size
+    }
+}
+
+const CD: Pattern = Pattern::new("$ cd <dir>");
+const LS: Pattern = Pattern::new("$ ls");
+
+pub struct FileSystem {
+    root: Rc<RefCell<File>>,
+    cwd: Weak<RefCell<File>>,
+}
+
+impl FileSystem {
+    fn new(root_path: &str) -> FileSystem {
+        let root = Rc::new(RefCell::new(File::new(root_path, &None)));
+        let w_root = Rc::downgrade(&root);
+        FileSystem { root, cwd: w_root }
+    }
+
+    pub fn root(&self) -> &Rc<RefCell<File>> {
+        &self.root
+    }
+
+    /// Rebuild the file system from the commands and their output
+    pub fn parse_from_terminal_output(str: &str) -> Result<FileSystem> {
+        let (fs, problems) = FileSystem::replay(str);
+        match problems.into_iter().next() {
+            Some(problem) => Err(problem),
+            None => Ok(fs),
+        }
+    }
+
+    /// Rebuild the file system, skipping lines that can't be understood.
+    /// Returns the problems of all skipped lines as well.
+    fn replay(str: &str) -> (FileSystem, Vec<Error>) {
+        let mut fs = FileSystem::new("/");
+        let mut problems = vec![];
+
+        // Assume root is always "/" and first line of input always is "cd /"
+        let mut lines = parse::lines(str);
+        if let Some(line) = lines.next().filter(|line| line.text != "$ cd /") {
+            problems.push(line.error(format!("expected '$ cd /', found '{}'", line.text)));
+        }
+
+        for line in lines {
+            if let Err(problem) = fs.parse_terminal_line(line) {
+                problems.push(problem);
+            }
+        }
+
+        (fs, problems)
+    }
+
+    fn parse_terminal_line(&mut self, line: Span) -> Result<()> {
+        if let Some([dir]) = CD.captures(line) {
+            self.cd(dir.text);
+        } else if LS.captures::<0>(line).is_some() {
+            // Since ls is the only command that produces output we don't need to remember it
+        } else if line.text.starts_with('$') {
+            return Err(line.error(format!("invalid command '{}'", line.text)));
+        } else {
+            // ls lists either "dir <name>" or "<size> <name>"
+            let (kind, name) = line.key_value(" ")?;
+            match kind.text {
+                "dir" => self.ls_file(name.text, true, 0),
+                _ => self.ls_file(name.text, false, kind.parse("size")?),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn tree_impl(current: &Rc<RefCell<File>>, layer: usize, tree: &mut String) {
+        for f in &current.borrow().files {
+            tree.push_str(&format!(
+                "{}- {} [{}]\n",
+                "  ".repeat(layer),
+                f.borrow().info_str(),
+                File::total_size(f)
+            ));
+            if f.borrow().is_dir {
+                FileSystem::tree_impl(f, layer + 1, tree);
+            }
+        }
+    }
+
+    /// Print all files below the root with their sizes
+    pub fn tree(&self) {
+        print!("{}", FileSystem::subtree(&self.root));
+    }
+
+    /// All files below a directory with their sizes, one per line
+    pub fn subtree(dir: &Rc<RefCell<File>>) -> String {
+        let mut tree = format!(
+            "- {}  [{}]\n",
+            dir.borrow().info_str(),
+            File::total_size(dir)
+        );
+        FileSystem::tree_impl(dir, 1, &mut tree);
+        tree
+    }
+
+    fn filter_dirs_impl<P>(
+        &self,
+        current: &Rc<RefCell<File>>,
+        res: &mut Vec<Rc<RefCell<File>>>,
+        predicate: &P,
+    ) where
+        Self: Sized,
+        P: Fn(&Rc<RefCell<File>>) -> bool,
+    {
+        for f in &current.borrow().files {
+            if f.borrow().is_dir {
+                if predicate(f) {
+                    res.push(f.clone());
+                }
+
+                self.filter_dirs_impl(f, res, predicate);
+            }
+        }
+    }
+
+    /// All directories below the root matching the predicate
+    pub fn filter_dirs<P>(&self, predicate: P) -> Vec<Rc<RefCell<File>>>
+    where
+        Self: Sized,
+        P: Fn(&Rc<RefCell<File>>) -> bool,
+    {
+        let mut dirs = vec![];
+        self.filter_dirs_impl(&self.root, &mut dirs, &predicate);
+        dirs
+    }
+
+    fn size_infos_impl(&self, current: &Rc<RefCell<File>>, res: &mut Vec<usize>) {
+        for f in &current.borrow().files {
+            if f.borrow().is_dir {
+                res.push(File::total_size(f));
+                self.size_infos_impl(f, res);
+            }
+        }
+    }
+
+    /// Total sizes of all directories below the root
+    pub fn size_infos(&self) -> Vec<usize> {
+        let mut size_infos = vec![];
+        self.size_infos_impl(&self.root, &mut size_infos);
+        size_infos
+    }
+
+    fn ls_file(&mut self, name: &str, is_dir: bool, size: usize) {
+        if self.exists(name).is_none() {
+            self.touch(name, is_dir, size);
+        }
+    }
+
+    fn exists(&self, name: &str) -> Option<Rc<RefCell<File>>> {
+        self.cwd
+            .upgrade()
+            .unwrap()
+            .borrow()
+            .files
+            .iter()
+            .find(|f| f.borrow().name == *name)
+            .cloned()
+    }
+
+    fn touch(&mut self, name: &str, is_dir: bool, size: usize) -> Weak<RefCell<File>> {
+        let f = Rc::new(RefCell::new(File::new(name, &Some(self.cwd.clone()))));
+        f.borrow_mut().is_dir = is_dir;
+        f.borrow_mut().size = size;
+        let wf = Rc::downgrade(&f);
+        self.cwd.upgrade().unwrap().borrow_mut().files.push(f);
+        wf
+    }
+
+    fn cd(&mut self, dir: &str) {
+        if dir == ".." {
+            if let Some(parent) = &self.cwd.upgrade().unwrap().borrow().parent {
+                self.cwd = parent.clone();
+                return;
+            }
+        }
+
+        if let Some(f) = self.exists(dir) {
+            self.cwd = Rc::downgrade(&f);
+            return;
+        }
+
+        self.cwd = self.touch(dir, true, 0);
+    }
+}
+
+/// REPL state of day 7, a working directory to move around the file system
+pub struct ShellSession {
+    /// Directories from the root to the working directory
+    path: Vec<Rc<RefCell<File>>>,
+}
+
+impl ShellSession {
+    pub fn open(input: &str) -> Result<Box<dyn Session>> {
+        let fs = FileSystem::parse_from_terminal_output(input)?;
+        Ok(Box::new(ShellSession {
+            path: vec![fs.root.clone()],
+        }))
+    }
+
+    fn cwd(&self) -> &Rc<RefCell<File>> {
+        self.path.last().unwrap()
+    }
+}
+
+impl Session for ShellSession {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("ls", "List the working directory"),
+            (
+                "cd <DIR>",
+                "Change the working directory, .. goes up and / to the root",
+            ),
+            ("pwd", "Print the working directory"),
+            ("du", "Total size of the working directory"),
+            ("tree", "Show everything below the working directory"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> std::result::Result<String, String> {
+        match command {
+            "ls" => Ok(self
+                .cwd()
+                .borrow()
+                .files()
+                .iter()
+                .map(|f| format!("{}\n", f.borrow().info_str()))
+                .collect()),
+            "cd" => {
+                match repl::arg::<String>(args, 0, "directory")?.as_str() {
+                    "/" => self.path.truncate(1),
+                    ".." if self.path.len() > 1 => {
+                        self.path.pop();
+                    }
+                    ".." => return Err(String::from("already at the root")),
+                    name => {
+                        let dir = self
+                            .cwd()
+                            .borrow()
+                            .files()
+                            .iter()
+                            .find(|f| f.borrow().is_dir() && f.borrow().name() == name)
+                            .cloned()
+                            .ok_or(format!("no directory '{name}'"))?;
+                        self.path.push(dir);
+                    }
+                }
+                Ok(String::from(""))
+            }
+            "pwd" => {
+                let names = self.path[1..]
+                    .iter()
+                    .map(|dir| dir.borrow().name().to_string())
+                    .collect::<Vec<_>>();
+                Ok(format!("/{}", names.join("/")))
+            }
+            "du" => Ok(File::total_size(self.cwd()).to_string()),
+            "tree" => Ok(FileSystem::subtree(self.cwd())),
+            _ => Err(repl::unknown(command)),
+        }
+    }
+}
+
+/// Random terminal output exploring the whole file system, which uses less
+/// than 40000000 bytes so there is a directory to delete
+pub fn generate(gen: &mut Gen) -> String {
+    let mut output = String::from("$ cd /\n");
+    // stay below the used space which still leaves a directory to delete
+    let mut budget = 40_000_000;
+    generate_dir(gen, &mut output, 0, &mut budget);
+    output
+}
+
+/// Terminal output listing a random directory and then visiting its subdirectories
+fn generate_dir(gen: &mut Gen, output: &mut String, depth: usize, budget: &mut usize) {
+    let word = |gen: &mut Gen| {
+        let len = gen.range(1..=6);
+        (0..len)
+            .map(|_| (b'a' + gen.range(0..=25) as u8) as char)
+            .collect::<String>()
+    };
+
+    // the root has at least one subdirectory so there is one to delete
+    let dirs = match depth {
+        0 => gen.list(1, word),
+        1..=3 => (0..gen.range(0..=2)).map(|_| word(gen)).collect(),
+        _ => vec![],
+    };
+    // the index keeps the names unique
+    let dirs = dirs
+        .into_iter()
+        .enumerate()
+        .map(|(i, dir)| format!("{dir}{i}"))
+        .collect::<Vec<_>>();
+    let mut entries = dirs
+        .iter()
+        .map(|dir| format!("dir {dir}"))
+        .collect::<Vec<_>>();
+    for i in 0..gen.range(0..=4) {
+        if *budget == 0 {
+            break;
+        }
+        let size = gen.range(1..=300_000.min(*budget));
+        *budget -= size;
+        entries.push(format!("{size} {}{i}.txt", word(gen)));
+    }
+    gen.shuffle(&mut entries);
+
+    output.push_str("$ ls\n");
+    for entry in entries {
+        output.push_str(&entry);
+        output.push('\n');
+    }
+    for dir in dirs {
+        output.push_str(&format!("$ cd {dir}\n"));
+        generate_dir(gen, output, depth + 1, budget);
+        output.push_str("$ cd ..\n");
+    }
+}
+
+example_tests!(Day7);
@@ -0,0 +1,266 @@
+use crate::{
+    error::{read_input, Error, Result},
+    grid::{Direction, Grid, Point},
+    parse,
+    prop::{Gen, Generate},
+    repl::{self, Session},
+    solution::{example_tests, Example, Solution},
+};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Grid<u32>;
+
+    const YEAR: u32 = super::YEAR;
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("21"),
+        part2: Some("8"),
+    }];
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_forest(input)
+    }
+
+    fn part1(&self, forest: &Self::Input) -> Result<String> {
+        Ok(count_visible(forest).to_string())
+    }
+
+    fn part2(&self, forest: &Self::Input) -> Result<String> {
+        Ok(max_scenic_score(forest).to_string())
+    }
+
+    fn check(&self, input: &str) -> Vec<Error> {
+        let width = input.lines().next().map_or(0, |row| row.chars().count());
+        let mut problems = vec![];
+        for row in parse::lines(input) {
+            problems.extend(
+                row.chars()
+                    .filter(|(_, c)| !c.is_ascii_digit())
+                    .map(|(tree, c)| tree.error(format!("invalid tree height '{c}'"))),
+            );
+            let found = row.text.chars().count();
+            if found != width {
+                problems.push(row.error(format!(
+                    "expected {width} characters per row, found {found}"
+                )));
+            }
+        }
+        problems
+    }
+
+    fn generator(&self) -> Option<Generate> {
+        Some(generate)
+    }
+
+    fn session(&self) -> Option<repl::Open> {
+        Some(ForestSession::open)
+    }
+}
+
+const EXAMPLE: &str = "\
+30373
+25512
+65332
+33549
+35390
+";
+
+pub fn visible_trees(filename: &str) -> Result<u32> {
+    read_input(filename).and_then(|content| visible_trees_str(&content))
+}
+
+pub fn visible_trees_str(input: &str) -> Result<u32> {
+    Ok(count_visible(&parse_forest(input)?))
+}
+
+pub fn highest_scenic_score(filename: &str) -> Result<usize> {
+    read_input(filename).and_then(|content| highest_scenic_score_str(&content))
+}
+
+pub fn highest_scenic_score_str(input: &str) -> Result<usize> {
+    Ok(max_scenic_score(&parse_forest(input)?))
+}
+
+/// Number of trees visible from outside the forest, looking along every row and
+/// column from both ends and marking the trees taller than all before them
+pub fn count_visible(forest: &Grid<u32>) -> u32 {
+    let (width, height) = (forest.width(), forest.height());
+    let mut visible = Grid::new(width, height, false);
+    let mut look = |line: &mut dyn Iterator<Item = Point>| {
+        let mut tallest = None;
+        for tree in line {
+            if tallest.is_none_or(|tallest| forest[tree] > tallest) {
+                visible[tree] = true;
+                tallest = Some(forest[tree]);
+            }
+        }
+    };
+
+    for y in 0..height {
+        look(&mut (0..width).map(|x| Point::new(x, y)));
+        look(&mut (0..width).rev().map(|x| Point::new(x, y)));
+    }
+    for x in 0..width {
+        look(&mut (0..height).map(|y| Point::new(x, y)));
+        look(&mut (0..height).rev().map(|y| Point::new(x, y)));
+    }
+    visible.points().filter(|tree| visible[*tree]).count() as u32
+}
+
+/// Same as [`count_visible`] but walks from every tree to the edges,
+/// kept as the simple reference
+pub fn count_visible_walking(forest: &Grid<u32>) -> u32 {
+    forest
+        .points()
+        .filter(|tree| is_visible(forest, *tree))
+        .count() as u32
+}
+
+fn max_scenic_score(forest: &Grid<u32>) -> usize {
+    forest
+        .points()
+        .map(|tree| scenic_score(forest, tree))
+        .max()
+        .unwrap_or(0)
+}
+
+fn scenic_score(forest: &Grid<u32>, tree: Point) -> usize {
+    Direction::ORTHOGONAL
+        .iter()
+        .map(|direction| viewing_distance(forest, tree, *direction))
+        .product()
+}
+
+/// Number of trees seen until the view is blocked, including the blocking one
+fn viewing_distance(forest: &Grid<u32>, tree: Point, direction: Direction) -> usize {
+    let height = forest[tree];
+
+    let mut distance = 0;
+    for (_, other) in forest.walk(tree, direction) {
+        distance += 1;
+        if *other >= height {
+            break;
+        }
+    }
+    distance
+}
+
+fn is_visible(forest: &Grid<u32>, tree: Point) -> bool {
+    let height = forest[tree];
+
+    // trees at the edge have nothing in their way in at least one direction
+    Direction::ORTHOGONAL.iter().any(|direction| {
+        forest
+            .walk(tree, *direction)
+            .all(|(_, other)| *other < height)
+    })
+}
+
+fn parse_forest(content: &str) -> Result<Grid<u32>> {
+    Grid::parse(content, |c| {
+        c.to_digit(10)
+            .ok_or_else(|| format!("invalid tree height '{c}'"))
+    })
+}
+
+/// REPL state of day 8, trees are queried by their 0 based column and row
+pub struct ForestSession {
+    forest: Grid<u32>,
+}
+
+impl ForestSession {
+    pub fn open(input: &str) -> Result<Box<dyn Session>> {
+        Ok(Box::new(ForestSession {
+            forest: parse_forest(input)?,
+        }))
+    }
+
+    fn tree(&self, args: &[&str]) -> std::result::Result<Point, String> {
+        let tree = Point::new(repl::arg(args, 0, "column")?, repl::arg(args, 1, "row")?);
+        match self.forest.contains(tree) {
+            true => Ok(tree),
+            false => Err(format!(
+                "({}, {}) is outside the {}x{} forest",
+                tree.x,
+                tree.y,
+                self.forest.width(),
+                self.forest.height()
+            )),
+        }
+    }
+}
+
+impl Session for ForestSession {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("size", "Width and height of the forest"),
+            ("height <X> <Y>", "Height of the tree in column X and row Y"),
+            (
+                "score <X> <Y>",
+                "Scenic score of a tree and its viewing distances",
+            ),
+            (
+                "visible <X> <Y>",
+                "Whether a tree is visible from outside the forest",
+            ),
+            ("best", "Tree with the highest scenic score"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> std::result::Result<String, String> {
+        let forest = &self.forest;
+        match command {
+            "size" => Ok(format!("{}x{}", forest.width(), forest.height())),
+            "height" => Ok(forest[self.tree(args)?].to_string()),
+            "score" => {
+                let tree = self.tree(args)?;
+                let distances = Direction::ORTHOGONAL
+                    .iter()
+                    .map(|direction| {
+                        let name = format!("{direction:?}").to_lowercase();
+                        format!("{name} {}", viewing_distance(forest, tree, *direction))
+                    })
+                    .collect::<Vec<_>>();
+                Ok(format!(
+                    "{} ({})",
+                    scenic_score(forest, tree),
+                    distances.join(", ")
+                ))
+            }
+            "visible" => Ok(is_visible(forest, self.tree(args)?).to_string()),
+            "best" => {
+                let best = forest
+                    .points()
+                    .max_by_key(|tree| scenic_score(forest, *tree))
+                    .ok_or(String::from("the forest is empty"))?;
+                Ok(format!(
+                    "({}, {}) with score {}",
+                    best.x,
+                    best.y,
+                    scenic_score(forest, best)
+                ))
+            }
+            _ => Err(repl::unknown(command)),
+        }
+    }
+}
+
+/// Random grid of tree heights, up to [`Gen::size`] trees wide and high
+pub fn generate(gen: &mut Gen) -> String {
+    let width = gen.range(1..=gen.size().max(1));
+    let height = gen.range(1..=gen.size().max(1));
+    (0..height)
+        .map(|_| {
+            let row = (0..width)
+                .map(|_| char::from_digit(gen.range(0..=9) as u32, 10).unwrap())
+                .collect::<String>();
+            format!("{row}\n")
+        })
+        .collect()
+}
+
+example_tests!(Day8);
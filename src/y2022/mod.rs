@@ -0,0 +1,12 @@
+//! Advent of Code 2022
+
+pub const YEAR: u32 = 2022;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
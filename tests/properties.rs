@@ -0,0 +1,187 @@
+use std::{collections::HashSet, io::BufReader};
+
+use adventofcode::{
+    prop::{self, Gen},
+    solution::Part,
+    y2022::{
+        day01, day02, day03, day04, day04::SectionAssignment, day05::Stack, day05::StackMovement,
+        day06, day07, day07::File, day07::FileSystem,
+    },
+    REGISTRY,
+};
+
+#[test]
+fn generated_inputs_are_solved() {
+    for day in REGISTRY {
+        // days without a generator, e.g. new ones, have nothing to check
+        let Some(generate) = day.generator() else {
+            continue;
+        };
+        // the day is part of the value to show it in the failure message
+        prop::check(
+            |gen| (day.day(), generate(gen)),
+            |(_, input)| {
+                day.check(input).is_empty()
+                    && day.parse(input).is_ok_and(|parsed| {
+                        [Part::One, Part::Two]
+                            .iter()
+                            .all(|part| day.solve(&*parsed, *part).is_ok())
+                    })
+            },
+        );
+    }
+}
+
+#[test]
+fn generators_repeat_inputs_of_a_seed() {
+    for generate in REGISTRY.iter().filter_map(|day| day.generator()) {
+        let input = generate(&mut Gen::new(5, 20));
+        assert!(!input.is_empty());
+        assert_eq!(input, generate(&mut Gen::new(5, 20)));
+    }
+}
+
+#[test]
+fn streaming_matches_parsed_input() {
+    let solve = |day: u32, input: &str, part| {
+        let day = REGISTRY.iter().find(|d| d.day() == day).unwrap();
+        day.solve(&*day.parse(input).unwrap(), part).unwrap()
+    };
+    // small buffers so lines are split between reads
+    fn buffered(input: &str) -> BufReader<&[u8]> {
+        BufReader::with_capacity(7, input.as_bytes())
+    }
+
+    prop::check(day01::generate, |input| {
+        day01::top_n_elves_reader(buffered(input), 3)
+            .unwrap()
+            .to_string()
+            == solve(1, input, Part::Two)
+    });
+    prop::check(day02::generate, |input| {
+        day02::total_points_reader(buffered(input), true)
+            .unwrap()
+            .to_string()
+            == solve(2, input, Part::One)
+    });
+    prop::check(day03::generate, |input| {
+        day03::sum_priorities_reader(buffered(input))
+            .unwrap()
+            .to_string()
+            == solve(3, input, Part::One)
+            && day03::sum_group_priorities_reader(buffered(input))
+                .unwrap()
+                .to_string()
+                == solve(3, input, Part::Two)
+    });
+    prop::check(day04::generate, |input| {
+        day04::sum_full_overlaps_reader(buffered(input))
+            .unwrap()
+            .to_string()
+            == solve(4, input, Part::One)
+    });
+    prop::check(day06::generate, |input| {
+        day06::chars_before_marker_reader::<14, _>(buffered(input))
+            .unwrap()
+            .to_string()
+            == solve(6, input, Part::Two)
+    });
+}
+
+#[test]
+fn top_n_elves_is_monotonic() {
+    prop::check(day01::generate, |input| {
+        let elves = input.split("\n\n").count();
+        let totals = (0..=elves + 1)
+            .map(|n| day01::top_n_elves_str(input, n).unwrap())
+            .collect::<Vec<_>>();
+        totals.windows(2).all(|pair| pair[0] <= pair[1])
+    });
+}
+
+#[test]
+fn rucksacks_share_exactly_one_item() {
+    let common = |items: &[&str]| {
+        items[0]
+            .chars()
+            .filter(|c| items.iter().all(|other| other.contains(*c)))
+            .collect::<HashSet<_>>()
+            .len()
+    };
+    prop::check(day03::generate, |input| {
+        let lines = input.lines().collect::<Vec<_>>();
+        lines.iter().all(|line| {
+            let (first, second) = line.split_at(line.len() / 2);
+            common(&[first, second]) == 1
+        }) && lines.chunks(3).all(|group| common(group) == 1)
+    });
+}
+
+#[test]
+fn full_overlap_implies_partial_overlap() {
+    let assignment = |gen: &mut Gen| {
+        let start = gen.range(1..=20) as u32;
+        let end = start + gen.range(0..=20) as u32;
+        (start, end)
+    };
+    prop::check(
+        |gen| (assignment(gen), assignment(gen)),
+        |((start1, end1), (start2, end2))| {
+            let first = SectionAssignment::new(*start1, *end1);
+            let second = SectionAssignment::new(*start2, *end2);
+            !first.full_overlap(&second) || first.partial_overlap(&second)
+        },
+    );
+}
+
+#[test]
+fn moving_crates_keeps_them() {
+    let procedure = |gen: &mut Gen| {
+        let stacks = gen.list(2, |gen| gen.list(0, |gen| *gen.pick(&['A', 'B', 'C'])));
+        let count = stacks.len();
+        let movement = (
+            gen.range(0..=10),
+            gen.range(0..=count - 1),
+            gen.range(0..=count - 1),
+        );
+        (stacks, movement)
+    };
+    prop::check(procedure, |(items, (n, from, to))| {
+        let crates = |stacks: &Vec<Stack>| stacks.iter().map(|s| s.items().len()).sum::<usize>();
+        let mut stacks = items
+            .iter()
+            .map(|items| Stack::from_items(items))
+            .collect::<Vec<_>>();
+        let mut in_order = stacks.clone();
+        let before = crates(&stacks);
+
+        stacks.move_n(*n, *from, *to);
+        in_order.move_n_in_order(*n, *from, *to);
+        crates(&stacks) == before && crates(&in_order) == before
+    });
+}
+
+#[test]
+fn directory_sizes_are_the_sum_of_their_children() {
+    prop::check(day07::generate, |input| {
+        let fs = FileSystem::parse_from_terminal_output(input).unwrap();
+        let mut dirs = fs.filter_dirs(|_| true);
+        dirs.push(fs.root().clone());
+        let sums_match = dirs.iter().all(|dir| {
+            let children = dir
+                .borrow()
+                .files()
+                .iter()
+                .map(File::total_size)
+                .sum::<usize>();
+            File::total_size(dir) == children
+        });
+
+        // every listed file is counted exactly once by the root
+        let listed = input
+            .lines()
+            .filter_map(|line| line.split_once(' ')?.0.parse::<usize>().ok())
+            .sum::<usize>();
+        sums_match && File::total_size(fs.root()) == listed
+    });
+}
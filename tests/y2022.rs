@@ -0,0 +1,293 @@
+use std::io::BufReader;
+
+use adventofcode::{
+    answers,
+    error::Error,
+    runner,
+    solution::{check_examples, Part},
+    y2022::{
+        day01, day02, day03, day04, day04::SectionAssignment, day05, day05::StackInspect,
+        day05::StackMovement, day06, day06::CharMarker, day07::FileSystem, day08,
+    },
+    REGISTRY,
+};
+
+#[test]
+fn registry_examples_pass() {
+    for day in REGISTRY {
+        for part in [Part::One, Part::Two] {
+            let failures = check_examples(*day, part);
+            assert!(failures.is_empty(), "{}", failures.join("\n"));
+        }
+    }
+}
+
+#[test]
+fn solver_functions_take_input_or_file() {
+    assert_eq!(day01::top_n_elves_str("1\n2\n\n4\n", 1).unwrap(), 4);
+    // totals don't fit in the u32 calories of a line
+    let elves = "4294967295\n1\n\n4294967295\n\n4294967295\n";
+    assert_eq!(
+        day01::top_n_elves_str(elves, 3).unwrap(),
+        3 * 4294967295 + 1
+    );
+    let day = REGISTRY.iter().find(|d| d.day() == 1).unwrap();
+    let parsed = day.parse(elves).unwrap();
+    assert_eq!(day.solve(parsed.as_ref(), Part::One).unwrap(), "4294967296");
+    // wider than high, rows and columns must not get mixed up
+    assert_eq!(
+        day08::visible_trees_str("11111\n12221\n11111\n").unwrap(),
+        15
+    );
+    assert_eq!(
+        day08::highest_scenic_score_str("11111\n12221\n11111\n").unwrap(),
+        1
+    );
+    assert!(matches!(
+        day08::visible_trees("does/not/exist.txt"),
+        Err(Error::Io { .. })
+    ));
+    assert!(matches!(
+        day08::visible_trees_str("12\n3x\n"),
+        Err(Error::Parse {
+            line: 2,
+            column: 2,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn streaming_solvers_read_files_and_small_buffers() {
+    // a one byte buffer splits every line and multi byte read
+    let reader = |input: &'static str| BufReader::with_capacity(1, input.as_bytes());
+    assert_eq!(
+        day01::top_n_elves_reader(reader("1\r\n2\n\n\n4\n\n3"), 2).unwrap(),
+        7
+    );
+    assert_eq!(
+        day02::total_points_reader(reader("A Y\n\nB X\nC Z"), false).unwrap(),
+        12
+    );
+    assert_eq!(
+        day03::sum_group_priorities_reader(reader("ab\nbc\nbd\n")).unwrap(),
+        2
+    );
+    assert_eq!(
+        day04::sum_partial_overlaps_reader(reader("2-4,6-8\n5-7,7-9\n")).unwrap(),
+        1
+    );
+    assert_eq!(
+        day06::chars_before_marker_reader::<4, _>(reader("mjqjpqmgbljsphdztnvjfqwrcgsmlb"))
+            .unwrap(),
+        7
+    );
+    assert!(matches!(
+        day03::sum_group_priorities_reader(reader("ab\nbc\n")),
+        Err(Error::Parse { line: 3, .. })
+    ));
+
+    let path = std::env::temp_dir().join(format!("aoc-streaming-{}.txt", std::process::id()));
+    std::fs::write(&path, "2-4,6-8\n2-8,3-7\n6-6,4-6\n").unwrap();
+    let filename = path.to_str().unwrap();
+    let full_overlaps = day04::sum_full_overlaps(filename);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(full_overlaps.unwrap(), 2);
+    assert!(matches!(
+        day06::chars_before_marker::<4>("does/not/exist.txt"),
+        Err(Error::Io { path, .. }) if path == "does/not/exist.txt"
+    ));
+}
+
+#[test]
+fn parse_validates_input_before_solving() {
+    let day = |n| REGISTRY.iter().find(|d| d.day() == n).unwrap();
+    assert!(matches!(
+        day(2).parse("A Y\nB W\n"),
+        Err(Error::Parse {
+            line: 2,
+            column: 3,
+            ..
+        })
+    ));
+    assert!(matches!(
+        day(6).parse("abcd\nefgh\n"),
+        Err(Error::Parse { line: 2, .. })
+    ));
+    // the stack count is checked against the drawing before stacks are created
+    assert!(matches!(
+        day(5).parse(" 1   99999999999\n\nmove 1 from 1 to 2\n"),
+        Err(Error::Parse {
+            line: 1,
+            column: 6,
+            ..
+        })
+    ));
+    // the solver functions validate like parse, counting characters of a single line
+    assert!(matches!(
+        day06::chars_before_marker_str::<4>("aébcd"),
+        Err(Error::Parse { column: 2, .. })
+    ));
+    assert!(matches!(
+        day06::chars_before_marker_str::<4>("abcd\nefgh\n"),
+        Err(Error::Parse { line: 2, .. })
+    ));
+    assert!(matches!(
+        day06::chars_before_marker_str::<4>("abcd\rx"),
+        Err(Error::Parse { column: 5, .. })
+    ));
+    assert_eq!(day06::chars_before_marker_str::<4>("abcd\r\n").unwrap(), 4);
+    let parsed = day(6).parse("abcd\n").unwrap();
+    assert_eq!(day(6).solve(parsed.as_ref(), Part::One).unwrap(), "4");
+    assert!(day(6).solve(parsed.as_ref(), Part::Two).is_err());
+}
+
+#[test]
+fn runner_streams_inputs_of_days_that_can() {
+    let path = std::env::temp_dir().join(format!("aoc-runner-{}.txt", std::process::id()));
+    let filename = path.to_str().unwrap().to_string();
+    let day = |n| *REGISTRY.iter().find(|d| d.day() == n).unwrap();
+    let run = |n, input| {
+        std::fs::write(&path, input).unwrap();
+        runner::execute(&[day(n)], &[Part::One, Part::Two], |_| filename.clone(), 1)
+            .pop()
+            .unwrap()
+    };
+
+    let result = run(1, "1\n2\n\n4\n");
+    assert_eq!(result.input_hash, Some(answers::input_hash("1\n2\n\n4\n")));
+    let answers = result.parts.iter().map(|p| p.answer.as_deref().unwrap());
+    assert_eq!(answers.collect::<Vec<_>>(), ["4", "7"]);
+    // nothing was parsed
+    assert!(result.parts.iter().all(|p| p.parse_time.is_zero()));
+
+    // invalid input is an error of the day, a missing marker only of its part
+    assert!(matches!(
+        run(6, "abc1efghijklmnopq\n").error,
+        Some(Error::Parse { column: 4, .. })
+    ));
+    let result = run(6, "abcd\n");
+    assert!(result.error.is_none());
+    // hashed to the end, though solving stopped at the marker
+    assert_eq!(result.input_hash, Some(answers::input_hash("abcd\n")));
+    assert_eq!(result.parts[0].answer.as_deref().unwrap(), "4");
+    assert!(matches!(result.parts[1].answer, Err(Error::NoSolution(_))));
+
+    // days without a streaming solver parse the input once for both parts
+    let result = run(5, "[A]\n 1\n\nmove 1 from 1 to 1\n");
+    assert_eq!(result.parts[0].answer.as_deref().unwrap(), "A");
+    assert!(!result.parts[0].parse_time.is_zero());
+    assert_eq!(result.parts[0].parse_time, result.parts[1].parse_time);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn section_assignments_overlap() {
+    let a = SectionAssignment::new(2, 8);
+    let b = SectionAssignment::new(3, 7);
+    let c = SectionAssignment::new(8, 9);
+    assert!(a.full_overlap(&b) && a.partial_overlap(&b));
+    assert!(!a.full_overlap(&c) && a.partial_overlap(&c));
+    assert!(!b.partial_overlap(&c));
+}
+
+#[test]
+fn stacks_move_crates() {
+    let (mut stacks, movements) =
+        day05::parse_procedure("[A]\n[B] [C]\n 1   2\n\nmove 2 from 1 to 2\n").unwrap();
+    assert_eq!(stacks.top_items(), "AC");
+    assert_eq!(movements[0].n, 2);
+
+    let mut in_order = stacks.clone();
+    stacks.move_n(2, 0, 1);
+    in_order.move_n_in_order(2, 0, 1);
+    assert_eq!(stacks[1].items(), ['C', 'A', 'B']);
+    assert_eq!(in_order[1].items(), ['C', 'B', 'A']);
+}
+
+#[test]
+fn char_marker_finds_distinct_characters() {
+    assert_eq!(
+        CharMarker::<4>::find_index_after_marker("aabcdd").unwrap(),
+        5
+    );
+
+    let mut marker = CharMarker::<2>::create();
+    marker.push('x');
+    marker.push('x');
+    assert!(!marker.is_present());
+    marker.push('y');
+    assert!(marker.is_present());
+    assert_eq!(marker.total_chars(), 3);
+}
+
+#[test]
+fn file_system_sizes() {
+    let fs = FileSystem::parse_from_terminal_output(
+        "$ cd /\n$ ls\ndir a\n10 b.txt\n$ cd a\n$ ls\n5 c.txt\n",
+    )
+    .unwrap();
+    assert_eq!(fs.size_infos(), [5]);
+    assert_eq!(fs.filter_dirs(|_| true)[0].borrow().name(), "a");
+    assert!(FileSystem::parse_from_terminal_output("$ cd /\n$ rm -rf a\n").is_err());
+}
+
+#[test]
+fn repl_sessions_explore_input() {
+    let day = |n| REGISTRY.iter().find(|d| d.day() == n).unwrap();
+    let open = |n| day(n).session().unwrap();
+
+    let mut stacks = open(5)("[A]\n[B] [C]\n 1   2\n\nmove 2 from 1 to 2\n").unwrap();
+    assert_eq!(stacks.execute("top", &["0"]).unwrap(), "AC");
+    assert_eq!(stacks.execute("top", &[]).unwrap(), " B");
+    stacks.execute("crane", &["9001"]).unwrap();
+    assert_eq!(stacks.execute("top", &[]).unwrap(), " A");
+    assert!(stacks.execute("stacks", &["2"]).is_err());
+
+    let mut shell = open(7)("$ cd /\n$ ls\ndir a\n10 b.txt\n$ cd a\n$ ls\n5 c.txt\n").unwrap();
+    shell.execute("cd", &["a"]).unwrap();
+    assert_eq!(shell.execute("pwd", &[]).unwrap(), "/a");
+    assert_eq!(shell.execute("du", &[]).unwrap(), "5");
+    assert!(shell.execute("cd", &["b.txt"]).is_err());
+
+    let mut forest = open(8)("11111\n12221\n11111\n").unwrap();
+    assert_eq!(forest.execute("height", &["2", "1"]).unwrap(), "2");
+    assert_eq!(
+        forest.execute("score", &["1", "1"]).unwrap(),
+        "1 (up 1, down 1, left 1, right 1)"
+    );
+    assert!(day(1).session().is_none());
+}
+
+#[test]
+fn check_reports_every_problem() {
+    for day in REGISTRY {
+        for example in day.examples() {
+            assert!(day.check(example.input).is_empty());
+        }
+    }
+
+    let day = |n| REGISTRY.iter().find(|d| d.day() == n).unwrap();
+    assert_eq!(day(3).check("abcd\nabc\na1\n").len(), 2);
+    assert_eq!(day(7).check("$ cd /\n$ rm a\nfoo\n$ ls\n").len(), 2);
+    assert_eq!(day(8).check("12\n3x\n4\n").len(), 2);
+    assert!(matches!(
+        day03::sum_priorities_str("abc\n"),
+        Err(Error::Parse { line: 1, .. })
+    ));
+    // the group reader validates the items of every rucksack too
+    assert!(matches!(
+        day03::sum_group_priorities_str("! a\n! b\n! c\n"),
+        Err(Error::Parse {
+            line: 1,
+            column: 1,
+            ..
+        })
+    ));
+
+    let problem = &day(8).check("12\n3x\n")[0];
+    assert_eq!(
+        problem.diagnostic("trees.txt", "12\n3x\n"),
+        "error: invalid tree height 'x'\n --> trees.txt:2:2\n  |\n2 | 3x\n  |  ^\n"
+    );
+}